@@ -1,13 +1,23 @@
 use crate::{
+    CC,
     schedule::{
-        CourseCode,
+        CourseCode, CourseInfo,
         CourseTermOffering::{self, *},
     },
-    CC,
 };
 use std::collections::HashMap;
 
-pub fn courses() -> HashMap<CourseCode, (String, Option<u32>, CourseTermOffering)> {
+pub fn courses() -> HashMap<CourseCode, CourseInfo> {
+    listings()
+        .into_iter()
+        .map(|(code, (title, credits, offering))| {
+            let info = CourseInfo::from_listing(&code, title, credits, offering);
+            (code, info)
+        })
+        .collect()
+}
+
+fn listings() -> HashMap<CourseCode, (String, Option<u32>, CourseTermOffering)> {
     HashMap::from([
         (
             CC!("ARCH", 2300),
@@ -0,0 +1,286 @@
+//! Degree audit: every requirement a schedule is checked against, with what satisfies it and what is
+//! still missing.
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+
+pub use crate::geneds::Shortfall;
+use crate::geneds::{GenEd, gened_progress, req_progress};
+use crate::prereqs::CourseReq;
+use crate::schedule::{CourseCode, Schedule};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditKind {
+    /// The courses laid out in a program's semesters
+    ProgramCourses,
+    ProgramElective,
+    Core,
+    Foundation,
+    SkillAndPerspective,
+    Prereq,
+    /// Courses placed in terms they are not taught in
+    TermOffering,
+    /// Credit values chosen for variable-credit courses
+    CreditChoice,
+}
+
+impl fmt::Display for AuditKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditKind::ProgramCourses => write!(f, "Program courses"),
+            AuditKind::ProgramElective => write!(f, "Program elective"),
+            AuditKind::Core => write!(f, "Core"),
+            AuditKind::Foundation => write!(f, "Foundation"),
+            AuditKind::SkillAndPerspective => write!(f, "Skills & Perspectives"),
+            AuditKind::Prereq => write!(f, "Prerequisites"),
+            AuditKind::TermOffering => write!(f, "Term offerings"),
+            AuditKind::CreditChoice => write!(f, "Credit choices"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditStatus {
+    Met,
+    Unmet,
+}
+
+impl fmt::Display for AuditStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditStatus::Met => write!(f, "Met"),
+            AuditStatus::Unmet => write!(f, "Unmet"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub kind: AuditKind,
+    /// The program, elective group, GenEd or course the requirement belongs to
+    pub requirement: String,
+    pub status: AuditStatus,
+    /// Met only if courses that are not graded yet earn the grades the requirement asks for
+    #[serde(default)]
+    pub provisional: bool,
+    /// Scheduled courses counted towards the requirement
+    pub applied: Vec<CourseCode>,
+    pub shortfall: Option<Shortfall>,
+    /// Explanations for an unmet requirement beyond the shortfall
+    pub notes: Vec<String>,
+}
+
+impl AuditEntry {
+    /// An entry that is met exactly when there is no shortfall.
+    pub fn new(
+        kind: AuditKind,
+        requirement: impl Into<String>,
+        applied: Vec<CourseCode>,
+        shortfall: Option<Shortfall>,
+    ) -> Self {
+        AuditEntry {
+            kind,
+            requirement: requirement.into(),
+            status: match shortfall {
+                None => AuditStatus::Met,
+                Some(_) => AuditStatus::Unmet,
+            },
+            provisional: false,
+            applied,
+            shortfall,
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn is_met(&self) -> bool {
+        self.status == AuditStatus::Met
+    }
+
+    /// Mark the entry unmet for a reason the shortfall does not capture.
+    pub fn fail(&mut self, note: impl Into<String>) {
+        self.status = AuditStatus::Unmet;
+        self.notes.push(note.into());
+    }
+}
+
+impl fmt::Display for AuditEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}", self.status)?;
+        if self.provisional {
+            write!(f, ", provisional")?;
+        }
+        write!(f, "] {}: {}", self.kind, self.requirement)?;
+        if let Some(shortfall) = self.shortfall {
+            write!(f, " (missing {shortfall})")?;
+        }
+        for note in &self.notes {
+            write!(f, "; {note}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct Audit {
+    pub entries: Vec<AuditEntry>,
+}
+
+impl Audit {
+    /// Whether every requirement is met.
+    pub fn is_satisfied(&self) -> bool {
+        self.entries.iter().all(|e| e.is_met())
+    }
+
+    /// Whether the schedule depends on grades not earned yet.
+    pub fn is_provisional(&self) -> bool {
+        self.entries.iter().any(|e| e.provisional)
+    }
+
+    pub fn unmet(&self) -> impl Iterator<Item = &AuditEntry> {
+        self.entries.iter().filter(|e| !e.is_met())
+    }
+}
+
+impl fmt::Display for Audit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{entry}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Schedule {
+    /// Check the schedule against its programs, the GenEds, prerequisites, term offerings and credit
+    /// choices.
+    pub fn audit(&self) -> Result<Audit> {
+        let mut entries = Vec::new();
+        let sched_courses: HashSet<&CourseCode> = std::iter::once(&self.incoming)
+            .chain(self.courses.iter())
+            .flatten()
+            .collect();
+
+        for prog_name in &self.programs {
+            let prog = self
+                .catalog
+                .programs
+                .iter()
+                .find(|p| p.name == *prog_name)
+                .ok_or_else(|| anyhow::anyhow!("Program {} not found in catalog", prog_name))?;
+            let planned: HashSet<&CourseCode> = self.courses.iter().flatten().collect();
+            let (applied, missing): (Vec<&CourseCode>, Vec<&CourseCode>) = prog
+                .semesters
+                .iter()
+                .flatten()
+                .partition(|code| planned.contains(code));
+            let mut entry = AuditEntry::new(
+                AuditKind::ProgramCourses,
+                &prog.name,
+                applied.into_iter().cloned().collect(),
+                (!missing.is_empty()).then_some(Shortfall::Courses(missing.len())),
+            );
+            for code in missing {
+                entry.notes.push(format!("{code} is not scheduled"));
+            }
+            entries.push(entry);
+
+            for elective in &prog.electives {
+                let (applied, shortfall) = req_progress(&elective.req, &sched_courses, self);
+                entries.push(AuditEntry::new(
+                    AuditKind::ProgramElective,
+                    format!("{}: {}", prog.name, elective.name),
+                    applied,
+                    shortfall,
+                ));
+            }
+        }
+
+        for progress in gened_progress(self) {
+            let kind = match progress.gened {
+                GenEd::Core { .. } => AuditKind::Core,
+                GenEd::Foundation { .. } => AuditKind::Foundation,
+                GenEd::SkillAndPerspective { .. } => AuditKind::SkillAndPerspective,
+            };
+            let mut entry = AuditEntry::new(
+                kind,
+                progress.gened.name(),
+                progress.applied,
+                progress.shortfall,
+            );
+            for conflict in progress.conflicts {
+                entry.fail(conflict);
+            }
+            entries.push(entry);
+        }
+
+        for (sem_idx, sem) in self.courses.iter().enumerate() {
+            for code in sem {
+                let Some(req) = self.catalog.prereqs.get(code) else {
+                    continue;
+                };
+                if *req == CourseReq::NotRequired {
+                    continue;
+                }
+                let explanation = req.explain(self, code, sem_idx);
+                let mut entry = AuditEntry::new(
+                    AuditKind::Prereq,
+                    code.to_string(),
+                    explanation.applied_courses().into_iter().cloned().collect(),
+                    None,
+                );
+                for leaf in explanation.unmet_leaves() {
+                    entry.fail(match &leaf.missing {
+                        Some(missing) => format!("needs {missing}"),
+                        None => format!("requires {}", leaf.req),
+                    });
+                }
+                if explanation.by_consent {
+                    entry
+                        .notes
+                        .push("depends on instructor consent".to_string());
+                }
+                for leaf in explanation.provisional_leaves() {
+                    entry.provisional = true;
+                    entry.notes.push(format!(
+                        "provisional: requires {}, not graded yet",
+                        leaf.req
+                    ));
+                }
+                entries.push(entry);
+            }
+        }
+
+        let mut offerings = AuditEntry::new(AuditKind::TermOffering, "All terms", vec![], None);
+        for (sem_idx, sem) in self.courses.iter().enumerate() {
+            let term = self.term(sem_idx);
+            for code in sem {
+                if let Some(info) = self.catalog.courses.get(code)
+                    && !info.offering.is_offered(&term)
+                {
+                    offerings.fail(format!("{code} is not offered in {term}"));
+                }
+            }
+        }
+        entries.push(offerings);
+
+        let mut credits = AuditEntry::new(AuditKind::CreditChoice, "All courses", vec![], None);
+        let mut chosen: Vec<_> = self.credits.iter().collect();
+        chosen.sort_by_key(|(code, _)| code.to_string());
+        for (code, &chosen) in chosen {
+            let range = self.catalog.courses.get(code).and_then(|info| info.credits);
+            if !sched_courses.contains(code) {
+                credits.fail(format!("{code} has credits chosen but is not scheduled"));
+            } else if !range.is_some_and(|r| (r.min..=r.max).contains(&chosen)) {
+                credits.fail(format!(
+                    "{chosen} credits for {code} is outside the catalog range"
+                ));
+            } else {
+                credits.applied.push(code.clone());
+            }
+        }
+        entries.push(credits);
+
+        Ok(Audit { entries })
+    }
+}
@@ -0,0 +1,385 @@
+//! Differences between two catalog years, for advisors reviewing a new catalog.
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+
+use crate::geneds::{ElectiveReq, GenEd};
+use crate::prereqs::CourseReq;
+use crate::schedule::{Catalog, CourseCode, CourseTermOffering, Credits, Program};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum CatalogChange {
+    CourseAdded {
+        course: CourseCode,
+        title: String,
+    },
+    CourseRemoved {
+        course: CourseCode,
+        title: String,
+    },
+    CreditsChanged {
+        course: CourseCode,
+        old: Option<Credits>,
+        new: Option<Credits>,
+    },
+    OfferingChanged {
+        course: CourseCode,
+        old: CourseTermOffering,
+        new: CourseTermOffering,
+    },
+    /// A missing prereq entry is reported as [`CourseReq::NotRequired`].
+    PrereqChanged {
+        course: CourseCode,
+        old: CourseReq,
+        new: CourseReq,
+    },
+    ProgramAdded {
+        program: String,
+    },
+    ProgramRemoved {
+        program: String,
+    },
+    ProgramSemesterChanged {
+        program: String,
+        semester: usize,
+        added: Vec<CourseCode>,
+        removed: Vec<CourseCode>,
+    },
+    /// `None` on either side means the elective group does not exist in that catalog.
+    ElectiveChanged {
+        program: String,
+        elective: String,
+        old: Option<ElectiveReq>,
+        new: Option<ElectiveReq>,
+    },
+    GenEdCoursesChanged {
+        gened: String,
+        added: Vec<CourseCode>,
+        removed: Vec<CourseCode>,
+    },
+}
+
+fn codes_to_string(codes: &[CourseCode]) -> String {
+    codes
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn credits_to_string(credits: &Option<Credits>) -> String {
+    credits.map_or_else(|| "none".to_string(), |c| c.to_string())
+}
+
+fn elective_to_string(req: &Option<ElectiveReq>) -> String {
+    match req {
+        None => "none".to_string(),
+        Some(ElectiveReq::Set(codes)) => format!("all of {}", codes_to_string(codes)),
+        Some(ElectiveReq::SetOpts(opts)) => opts
+            .iter()
+            .map(|opt| format!("[{}]", codes_to_string(opt)))
+            .collect::<Vec<_>>()
+            .join(" or "),
+        Some(ElectiveReq::Courses { num, courses }) => {
+            format!("{num} of {}", codes_to_string(courses))
+        }
+        Some(ElectiveReq::Credits { num, courses }) => {
+            format!("{num} credits of {}", codes_to_string(courses))
+        }
+    }
+}
+
+impl CatalogChange {
+    /// The change as (kind, subject, old value, new value), as rendered in text and spreadsheet output.
+    pub fn columns(&self) -> [String; 4] {
+        match self {
+            CatalogChange::CourseAdded { course, title } => [
+                "Course added".into(),
+                course.to_string(),
+                String::new(),
+                title.clone(),
+            ],
+            CatalogChange::CourseRemoved { course, title } => [
+                "Course removed".into(),
+                course.to_string(),
+                title.clone(),
+                String::new(),
+            ],
+            CatalogChange::CreditsChanged { course, old, new } => [
+                "Credits changed".into(),
+                course.to_string(),
+                credits_to_string(old),
+                credits_to_string(new),
+            ],
+            CatalogChange::OfferingChanged { course, old, new } => [
+                "Offering changed".into(),
+                course.to_string(),
+                format!("{old:?}"),
+                format!("{new:?}"),
+            ],
+            CatalogChange::PrereqChanged { course, old, new } => [
+                "Prerequisites changed".into(),
+                course.to_string(),
+                old.to_string(),
+                new.to_string(),
+            ],
+            CatalogChange::ProgramAdded { program } => [
+                "Program added".into(),
+                program.clone(),
+                String::new(),
+                String::new(),
+            ],
+            CatalogChange::ProgramRemoved { program } => [
+                "Program removed".into(),
+                program.clone(),
+                String::new(),
+                String::new(),
+            ],
+            CatalogChange::ProgramSemesterChanged {
+                program,
+                semester,
+                added,
+                removed,
+            } => [
+                "Program semester changed".into(),
+                format!("{program}, semester {}", semester + 1),
+                codes_to_string(removed),
+                codes_to_string(added),
+            ],
+            CatalogChange::ElectiveChanged {
+                program,
+                elective,
+                old,
+                new,
+            } => [
+                "Elective changed".into(),
+                format!("{program}, {elective}"),
+                elective_to_string(old),
+                elective_to_string(new),
+            ],
+            CatalogChange::GenEdCoursesChanged {
+                gened,
+                added,
+                removed,
+            } => [
+                "GenEd courses changed".into(),
+                gened.clone(),
+                codes_to_string(removed),
+                codes_to_string(added),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for CatalogChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [kind, subject, old, new] = self.columns();
+        write!(f, "{kind}: {subject}")?;
+        match (old.is_empty(), new.is_empty()) {
+            (true, true) => Ok(()),
+            (false, true) => write!(f, " (was {old})"),
+            (true, false) => write!(f, " ({new})"),
+            (false, false) => write!(f, " ({old} -> {new})"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CatalogDiff {
+    pub old_year: u32,
+    pub new_year: u32,
+    pub changes: Vec<CatalogChange>,
+}
+
+impl CatalogDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl fmt::Display for CatalogDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Changes from the {}-{} catalog to the {}-{} catalog:",
+            self.old_year,
+            self.old_year + 1,
+            self.new_year,
+            self.new_year + 1
+        )?;
+        if self.changes.is_empty() {
+            writeln!(f, "  (none)")?;
+        }
+        for change in &self.changes {
+            writeln!(f, "  {change}")?;
+        }
+        Ok(())
+    }
+}
+
+fn sorted<'a>(codes: impl IntoIterator<Item = &'a CourseCode>) -> Vec<&'a CourseCode> {
+    let mut codes: Vec<_> = codes.into_iter().collect();
+    codes.sort_by_key(|c| c.to_string());
+    codes.dedup();
+    codes
+}
+
+// Courses in `new` but not `old`, and in `old` but not `new`
+fn set_changes(old: &[CourseCode], new: &[CourseCode]) -> (Vec<CourseCode>, Vec<CourseCode>) {
+    let old_set: HashSet<&CourseCode> = old.iter().collect();
+    let new_set: HashSet<&CourseCode> = new.iter().collect();
+    (
+        sorted(new_set.difference(&old_set).copied())
+            .into_iter()
+            .cloned()
+            .collect(),
+        sorted(old_set.difference(&new_set).copied())
+            .into_iter()
+            .cloned()
+            .collect(),
+    )
+}
+
+fn diff_program(old: &Program, new: &Program, changes: &mut Vec<CatalogChange>) {
+    let empty = Vec::new();
+    for semester in 0..old.semesters.len().max(new.semesters.len()) {
+        let (added, removed) = set_changes(
+            old.semesters.get(semester).unwrap_or(&empty),
+            new.semesters.get(semester).unwrap_or(&empty),
+        );
+        if !added.is_empty() || !removed.is_empty() {
+            changes.push(CatalogChange::ProgramSemesterChanged {
+                program: new.name.clone(),
+                semester,
+                added,
+                removed,
+            });
+        }
+    }
+
+    let names: BTreeSet<&String> = old
+        .electives
+        .iter()
+        .chain(new.electives.iter())
+        .map(|e| &e.name)
+        .collect();
+    for name in names {
+        let find = |prog: &Program| {
+            prog.electives
+                .iter()
+                .find(|e| &e.name == name)
+                .map(|e| e.req.clone())
+        };
+        let (old_req, new_req) = (find(old), find(new));
+        if old_req != new_req {
+            changes.push(CatalogChange::ElectiveChanged {
+                program: new.name.clone(),
+                elective: name.clone(),
+                old: old_req,
+                new: new_req,
+            });
+        }
+    }
+}
+
+fn gened_name(gened: &GenEd) -> &String {
+    match gened {
+        GenEd::Core { name, .. }
+        | GenEd::Foundation { name, .. }
+        | GenEd::SkillAndPerspective { name, .. } => name,
+    }
+}
+
+/// Everything that changed between the `old` and `new` catalogs.
+pub fn catalog_diff(old: &Catalog, new: &Catalog) -> CatalogDiff {
+    let mut changes = Vec::new();
+
+    for course in sorted(old.courses.keys().chain(new.courses.keys())) {
+        match (old.courses.get(course), new.courses.get(course)) {
+            (None, Some(info)) => changes.push(CatalogChange::CourseAdded {
+                course: course.clone(),
+                title: info.title.clone(),
+            }),
+            (Some(info), None) => changes.push(CatalogChange::CourseRemoved {
+                course: course.clone(),
+                title: info.title.clone(),
+            }),
+            (Some(old_info), Some(new_info)) => {
+                if old_info.credits != new_info.credits {
+                    changes.push(CatalogChange::CreditsChanged {
+                        course: course.clone(),
+                        old: old_info.credits,
+                        new: new_info.credits,
+                    });
+                }
+                if old_info.offering != new_info.offering {
+                    changes.push(CatalogChange::OfferingChanged {
+                        course: course.clone(),
+                        old: old_info.offering.clone(),
+                        new: new_info.offering.clone(),
+                    });
+                }
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+
+    for course in sorted(old.prereqs.keys().chain(new.prereqs.keys())) {
+        let old_req = old.prereqs.get(course).cloned().unwrap_or_default();
+        let new_req = new.prereqs.get(course).cloned().unwrap_or_default();
+        if old_req != new_req {
+            changes.push(CatalogChange::PrereqChanged {
+                course: course.clone(),
+                old: old_req,
+                new: new_req,
+            });
+        }
+    }
+
+    for old_prog in &old.programs {
+        match new.programs.iter().find(|p| p.name == old_prog.name) {
+            Some(new_prog) => diff_program(old_prog, new_prog, &mut changes),
+            None => changes.push(CatalogChange::ProgramRemoved {
+                program: old_prog.name.clone(),
+            }),
+        }
+    }
+    for new_prog in &new.programs {
+        if !old.programs.iter().any(|p| p.name == new_prog.name) {
+            changes.push(CatalogChange::ProgramAdded {
+                program: new_prog.name.clone(),
+            });
+        }
+    }
+
+    let names: BTreeSet<&String> = old
+        .geneds
+        .iter()
+        .chain(new.geneds.iter())
+        .map(gened_name)
+        .collect();
+    for name in names {
+        let codes = |catalog: &Catalog| {
+            catalog
+                .geneds
+                .iter()
+                .filter(|g| gened_name(g) == name)
+                .flat_map(|g| g.all_course_codes())
+                .collect::<Vec<_>>()
+        };
+        let (added, removed) = set_changes(&codes(old), &codes(new));
+        if !added.is_empty() || !removed.is_empty() {
+            changes.push(CatalogChange::GenEdCoursesChanged {
+                gened: name.clone(),
+                added,
+                removed,
+            });
+        }
+    }
+
+    CatalogDiff {
+        old_year: old.low_year,
+        new_year: new.low_year,
+        changes,
+    }
+}
@@ -0,0 +1,375 @@
+//! Earliest-term and critical-path analysis over the prerequisite graph, without running the solver.
+//!
+//! Every course gets the earliest semester it could be taken in if all its prerequisites were taken as
+//! early as possible, honoring term offerings. Requirements that are not courses (standing, consent,
+//! test scores, ...) are assumed to be met, so the result is a lower bound the solver can rely on.
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::prereqs::CourseReq;
+use crate::schedule::{Catalog, CourseCode, Program, Schedule, Term};
+
+/// When a course can be taken at the earliest.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Availability {
+    /// Already taken before the first semester
+    Incoming,
+    /// Index of the earliest semester
+    Semester(usize),
+    /// Not within the semesters considered
+    Unreachable,
+}
+
+impl Availability {
+    pub fn semester(&self) -> Option<usize> {
+        match self {
+            Availability::Semester(sem_idx) => Some(*sem_idx),
+            _ => None,
+        }
+    }
+}
+
+/// Semester-by-semester lower bounds for every course in a catalog.
+#[derive(Debug, Clone)]
+pub struct CriticalPath<'a> {
+    catalog: &'a Catalog,
+    /// The terms of the semesters considered, in order
+    pub terms: Vec<Term>,
+    // Slot 0 is incoming, slot `i + 1` is semester `i`, and `terms.len() + 1` is unreachable
+    slots: HashMap<CourseCode, usize>,
+    // The prerequisite that kept each course from being taken earlier
+    binding: HashMap<CourseCode, CourseCode>,
+}
+
+// Course codes in `req` that must be taken whichever alternatives are chosen, with whether the
+// dependency is strict (an earlier term)
+fn mandatory_leaves<'a>(req: &'a CourseReq, leaves: &mut Vec<(&'a CourseCode, bool)>) {
+    match req {
+        CourseReq::And(reqs) => {
+            for r in reqs {
+                mandatory_leaves(r, leaves);
+            }
+        }
+        CourseReq::Or(reqs) if reqs.len() == 1 => mandatory_leaves(&reqs[0], leaves),
+        CourseReq::PreCourse(code) | CourseReq::PreCourseGrade(code, _) => {
+            leaves.push((code, true))
+        }
+        CourseReq::CoCourse(code)
+        | CourseReq::CoCourseGrade(code, _)
+        | CourseReq::SameTermCourse(code) => leaves.push((code, false)),
+        _ => {}
+    }
+}
+
+fn all_leaves<'a>(req: &'a CourseReq, leaves: &mut Vec<&'a CourseCode>) {
+    match req {
+        CourseReq::And(reqs) | CourseReq::Or(reqs) => {
+            for r in reqs {
+                all_leaves(r, leaves);
+            }
+        }
+        CourseReq::PreCourse(code)
+        | CourseReq::CoCourse(code)
+        | CourseReq::PreCourseGrade(code, _)
+        | CourseReq::CoCourseGrade(code, _)
+        | CourseReq::SameTermCourse(code) => leaves.push(code),
+        _ => {}
+    }
+}
+
+impl<'a> CriticalPath<'a> {
+    /// Analyse `catalog` for a student who already has `incoming` and plans the semesters in `terms`.
+    pub fn new(catalog: &'a Catalog, incoming: &[CourseCode], terms: Vec<Term>) -> Self {
+        Self::with_not_before(catalog, incoming, terms, &HashMap::new())
+    }
+
+    /// Like [`CriticalPath::new`], but courses in `not_before` cannot be taken before the given
+    /// semester, e.g. to ask what happens if a student starts a sequence late.
+    pub fn with_not_before(
+        catalog: &'a Catalog,
+        incoming: &[CourseCode],
+        terms: Vec<Term>,
+        not_before: &HashMap<CourseCode, usize>,
+    ) -> Self {
+        let unreachable = terms.len() + 1;
+        let mut codes: HashSet<&CourseCode> = catalog
+            .courses
+            .keys()
+            .chain(catalog.prereqs.keys())
+            .chain(incoming)
+            .collect();
+        for req in catalog.prereqs.values() {
+            let mut leaves = Vec::new();
+            all_leaves(req, &mut leaves);
+            codes.extend(leaves);
+        }
+        let mut codes: Vec<&CourseCode> = codes.into_iter().collect();
+        codes.sort_by_key(|c| c.to_string());
+
+        let mut analysis = CriticalPath {
+            catalog,
+            terms,
+            slots: codes
+                .iter()
+                .map(|&code| (code.clone(), usize::from(!incoming.contains(code))))
+                .collect(),
+            binding: HashMap::new(),
+        };
+
+        // Raise every course from the bottom until nothing changes: the least fixed point, in which
+        // mutually co-requisite courses share a term and strict cycles become unreachable
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &code in &codes {
+                if incoming.contains(code) {
+                    continue;
+                }
+                let ready = match catalog.prereqs.get(code) {
+                    Some(req) => analysis.ready_slot(req).0,
+                    None => 0,
+                };
+                let floor = not_before.get(code).map_or(1, |&sem_idx| sem_idx + 1);
+                let slot = (ready.max(floor)..unreachable)
+                    .find(|&slot| analysis.is_offered(code, slot))
+                    .unwrap_or(unreachable);
+                if analysis.slots[code] != slot {
+                    analysis.slots.insert(code.clone(), slot);
+                    changed = true;
+                }
+            }
+        }
+
+        for &code in &codes {
+            if let Some(req) = catalog.prereqs.get(code)
+                && !incoming.contains(code)
+                && let (ready, Some(pred)) = analysis.ready_slot(req)
+                && ready > 1
+            {
+                analysis.binding.insert(code.clone(), pred.clone());
+            }
+        }
+        analysis
+    }
+
+    fn slot(&self, code: &CourseCode) -> usize {
+        self.slots.get(code).copied().unwrap_or(1)
+    }
+
+    fn is_offered(&self, code: &CourseCode, slot: usize) -> bool {
+        match self.catalog.courses.get(code) {
+            Some(info) => info.offering.is_offered(&self.terms[slot - 1]),
+            None => true,
+        }
+    }
+
+    // The first slot `req` is satisfied in, and the course that decides it
+    fn ready_slot<'r>(&self, req: &'r CourseReq) -> (usize, Option<&'r CourseCode>) {
+        let unreachable = self.terms.len() + 1;
+        match req {
+            CourseReq::And(reqs) => reqs
+                .iter()
+                .map(|r| self.ready_slot(r))
+                .max_by_key(|&(slot, _)| slot)
+                .unwrap_or((0, None)),
+            CourseReq::Or(reqs) => reqs
+                .iter()
+                .map(|r| self.ready_slot(r))
+                .min_by_key(|&(slot, _)| slot)
+                .unwrap_or((0, None)),
+            CourseReq::PreCourse(code) | CourseReq::PreCourseGrade(code, _) => {
+                ((self.slot(code) + 1).min(unreachable), Some(code))
+            }
+            CourseReq::CoCourse(code)
+            | CourseReq::CoCourseGrade(code, _)
+            | CourseReq::SameTermCourse(code) => (self.slot(code), Some(code)),
+            _ => (0, None),
+        }
+    }
+
+    fn availability(&self, slot: usize) -> Availability {
+        match slot {
+            0 => Availability::Incoming,
+            slot if slot > self.terms.len() => Availability::Unreachable,
+            slot => Availability::Semester(slot - 1),
+        }
+    }
+
+    /// The earliest semester `code` can be taken in. Courses the catalog knows nothing about can be
+    /// taken right away.
+    pub fn earliest(&self, code: &CourseCode) -> Availability {
+        self.availability(self.slot(code))
+    }
+
+    pub fn earliest_term(&self, code: &CourseCode) -> Option<Term> {
+        self.earliest(code)
+            .semester()
+            .map(|sem_idx| self.terms[sem_idx])
+    }
+
+    /// The chain of prerequisites that keeps `code` from being taken earlier, ending with `code`.
+    pub fn chain(&self, code: &CourseCode) -> Vec<CourseCode> {
+        let mut chain = vec![code.clone()];
+        while let Some(pred) = self.binding.get(chain.last().unwrap()) {
+            if chain.contains(pred) {
+                break;
+            }
+            chain.push(pred.clone());
+        }
+        chain.reverse();
+        chain
+    }
+
+    /// The longest prerequisite chain of `prog` and the slack of each of its required courses.
+    pub fn program_path(&self, prog: &Program) -> ProgramPath {
+        let required: Vec<&CourseCode> = prog
+            .semesters
+            .iter()
+            .flatten()
+            .filter(|code| self.slot(code) != 0)
+            .collect();
+
+        let longest_chain = required
+            .iter()
+            .map(|code| self.chain(code))
+            .max_by_key(|chain| (self.slot(chain.last().unwrap()), chain.len()))
+            .unwrap_or_default();
+
+        // The latest slot each required course (and each course those need regardless of the
+        // alternatives chosen) can be taken in while leaving room for everything depending on it;
+        // lowered from the top until nothing changes
+        let mut needed: Vec<&CourseCode> = required.clone();
+        let mut idx = 0;
+        while let Some(&code) = needed.get(idx) {
+            if let Some(req) = self.catalog.prereqs.get(code) {
+                let mut leaves = Vec::new();
+                mandatory_leaves(req, &mut leaves);
+                for (leaf, _) in leaves {
+                    if self.slot(leaf) != 0 && !needed.contains(&leaf) {
+                        needed.push(leaf);
+                    }
+                }
+            }
+            idx += 1;
+        }
+        let latest_offered = |code: &CourseCode, bound: usize| {
+            (1..=bound.min(self.terms.len()))
+                .rev()
+                .find(|&slot| self.is_offered(code, slot))
+                .unwrap_or(0)
+        };
+        let mut latest: HashMap<&CourseCode, usize> = needed
+            .iter()
+            .map(|&code| (code, latest_offered(code, usize::MAX)))
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &code in &needed {
+                let Some(req) = self.catalog.prereqs.get(code) else {
+                    continue;
+                };
+                let mut leaves = Vec::new();
+                mandatory_leaves(req, &mut leaves);
+                for (leaf, strict) in leaves {
+                    let Some(&leaf_latest) = latest.get(leaf) else {
+                        continue;
+                    };
+                    let bound = latest[code].saturating_sub(usize::from(strict));
+                    let slot = latest_offered(leaf, bound);
+                    if slot < leaf_latest {
+                        latest.insert(leaf, slot);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        let slack = required
+            .iter()
+            .map(|&code| CourseSlack {
+                code: code.clone(),
+                earliest: self.earliest(code).semester(),
+                latest: latest[code].checked_sub(1),
+            })
+            .collect();
+
+        ProgramPath {
+            program: prog.name.clone(),
+            longest_chain,
+            slack,
+        }
+    }
+}
+
+/// How early and how late a required course of a program can be taken.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CourseSlack {
+    pub code: CourseCode,
+    pub earliest: Option<usize>,
+    /// The last semester that still leaves room for the courses depending on it
+    pub latest: Option<usize>,
+}
+
+impl CourseSlack {
+    /// How many semesters the course can be put off; `None` if it cannot fit at all.
+    pub fn slack(&self) -> Option<usize> {
+        self.latest?.checked_sub(self.earliest?)
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProgramPath {
+    pub program: String,
+    /// The prerequisite chain ending in the required course that can be finished last, in order
+    pub longest_chain: Vec<CourseCode>,
+    pub slack: Vec<CourseSlack>,
+}
+
+impl ProgramPath {
+    /// Whether every required course fits in the semesters considered.
+    pub fn is_feasible(&self) -> bool {
+        self.slack.iter().all(|s| s.slack().is_some())
+    }
+}
+
+impl fmt::Display for ProgramPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{}: longest chain {}",
+            self.program,
+            self.longest_chain
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ")
+        )?;
+        for course in &self.slack {
+            match (course.earliest, course.slack()) {
+                (Some(earliest), Some(slack)) => writeln!(
+                    f,
+                    "  {}: semester {} at the earliest, slack {slack}",
+                    course.code,
+                    earliest + 1
+                )?,
+                _ => writeln!(f, "  {}: does not fit", course.code)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Schedule {
+    /// Earliest semesters for the catalog's courses, given this schedule's incoming courses and terms.
+    pub fn critical_path(&self) -> CriticalPath<'_> {
+        CriticalPath::new(
+            &self.catalog,
+            &self.incoming,
+            (0..self.courses.len())
+                .map(|sem_idx| self.term(sem_idx))
+                .collect(),
+        )
+    }
+}
@@ -1,8 +1,9 @@
-use crate::schedule::{Catalog, CourseCode, Schedule};
-use anyhow::Result;
+use crate::schedule::{CourseCode, Schedule};
+use anyhow::{Result, bail};
 use savefile_derive::Savefile;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 #[derive(Clone, Debug, Savefile, Serialize, Deserialize, Hash, Eq, PartialEq)]
 pub enum GenEd {
@@ -25,11 +26,39 @@ pub enum ElectiveReq {
     },
 }
 
+/// What is still missing from a requirement.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Shortfall {
+    Courses(usize),
+    Credits(u32),
+}
+
+impl fmt::Display for Shortfall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Shortfall::Courses(1) => write!(f, "1 course"),
+            Shortfall::Courses(num) => write!(f, "{num} courses"),
+            Shortfall::Credits(1) => write!(f, "1 credit"),
+            Shortfall::Credits(num) => write!(f, "{num} credits"),
+        }
+    }
+}
+
+impl GenEd {
+    pub fn name(&self) -> &str {
+        match self {
+            GenEd::Core { name, .. }
+            | GenEd::Foundation { name, .. }
+            | GenEd::SkillAndPerspective { name, .. } => name,
+        }
+    }
+}
+
 // Helper: for a ElectiveReq, return a set of courses from schedule that can be used to satisfy it, or None if not possible
-fn satisfy_req<'a>(
+pub(crate) fn satisfy_req<'a>(
     req: &'a ElectiveReq,
     sched_courses: &HashSet<&'a CourseCode>,
-    catalog: &Catalog,
+    sched: &Schedule,
 ) -> Option<HashSet<&'a CourseCode>> {
     match req {
         ElectiveReq::Set(codes) => {
@@ -76,21 +105,11 @@ fn satisfy_req<'a>(
                 .copied()
                 .collect();
             // Sort by credits descending
-            available.sort_by_key(|c| {
-                -(catalog
-                    .courses
-                    .get(*c)
-                    .and_then(|(_, cr, _)| *cr)
-                    .unwrap_or(0) as i32)
-            });
+            available.sort_by_key(|c| -(sched.course_credits(c) as i32));
             let mut selected = HashSet::new();
             let mut total = 0u32;
             for c in available {
-                let cr = catalog
-                    .courses
-                    .get(c)
-                    .and_then(|(_, cr, _)| *cr)
-                    .unwrap_or(0);
+                let cr = sched.course_credits(c);
                 selected.insert(c);
                 total += cr;
                 if total >= *num {
@@ -102,147 +121,678 @@ fn satisfy_req<'a>(
     }
 }
 
-pub fn are_geneds_satisfied(sched: &Schedule) -> Result<bool> {
-    let sched_courses: HashSet<&CourseCode> = std::iter::once(&sched.incoming)
-        .chain(sched.courses.iter())
-        .flatten()
-        .collect();
-    // 1. Core: each must be satisfied, overlap allowed
-    let mut all_core_ok = true;
-    for gened in sched.catalog.geneds.iter() {
-        if let GenEd::Core { req, .. } = gened {
-            if satisfy_req(req, &sched_courses, &sched.catalog).is_none() {
-                all_core_ok = false;
+/// The courses from `sched_courses` counted towards `req` and, if it is not met, what is still missing.
+/// `req` is met exactly when [`satisfy_req`] succeeds.
+pub(crate) fn req_progress(
+    req: &ElectiveReq,
+    sched_courses: &HashSet<&CourseCode>,
+    sched: &Schedule,
+) -> (Vec<CourseCode>, Option<Shortfall>) {
+    let present = |codes: &[CourseCode]| -> Vec<CourseCode> {
+        codes
+            .iter()
+            .filter(|c| sched_courses.contains(c))
+            .cloned()
+            .collect()
+    };
+    let missing_courses = |num: usize| (num > 0).then_some(Shortfall::Courses(num));
+    match req {
+        ElectiveReq::Set(codes) => {
+            let applied = present(codes);
+            let missing = codes.len() - applied.len();
+            (applied, missing_courses(missing))
+        }
+        ElectiveReq::SetOpts(opts) => opts
+            .iter()
+            .map(|opt| {
+                let applied = present(opt);
+                let missing = opt.len() - applied.len();
+                (applied, missing_courses(missing))
+            })
+            // The first complete option, as in satisfy_req, otherwise the closest one
+            .min_by_key(|(_, shortfall)| *shortfall)
+            .unwrap_or_default(),
+        ElectiveReq::Courses { num, courses } => {
+            let mut applied = present(courses);
+            applied.truncate(*num);
+            let missing = num - applied.len();
+            (applied, missing_courses(missing))
+        }
+        ElectiveReq::Credits { num, courses } => {
+            let mut available = present(courses);
+            // Sort by credits descending
+            available.sort_by_key(|c| -(sched.course_credits(c) as i32));
+            let mut applied = Vec::new();
+            let mut total = 0u32;
+            for c in available {
+                if total >= *num {
+                    break;
+                }
+                total += sched.course_credits(&c);
+                applied.push(c);
             }
+            let shortfall = (total < *num).then(|| Shortfall::Credits(num - total));
+            (applied, shortfall)
         }
     }
-    if !all_core_ok {
-        return Ok(false);
+}
+
+/// The scheduled courses counted towards one Foundation GenEd.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FoundationMatch<'a> {
+    pub name: &'a str,
+    pub req: &'a ElectiveReq,
+    /// In the order the requirement lists them
+    pub applied: Vec<CourseCode>,
+    pub shortfall: Option<Shortfall>,
+}
+
+/// An assignment of scheduled courses to the catalog's Foundation GenEds in which no course counts
+/// towards more than one Foundation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FoundationAssignment<'a> {
+    /// In catalog order
+    pub foundations: Vec<FoundationMatch<'a>>,
+    /// The search gave up before finding out whether the unmet Foundations could be met
+    pub undetermined: bool,
+}
+
+impl FoundationAssignment<'_> {
+    pub fn is_complete(&self) -> bool {
+        self.foundations.iter().all(|f| f.shortfall.is_none())
     }
 
-    // 2. Foundation: each must be satisfied, but no course can be used for more than one Foundation
-    let mut foundation_reqs = vec![];
-    let mut foundation_names = vec![];
-    for gened in sched.catalog.geneds.iter() {
-        if let GenEd::Foundation { req, name } = gened {
-            foundation_reqs.push(req);
-            foundation_names.push(name);
+    /// The Foundation `code` counts towards, if any.
+    pub fn foundation_of(&self, code: &CourseCode) -> Option<&str> {
+        self.foundations
+            .iter()
+            .find(|f| f.applied.contains(code))
+            .map(|f| f.name)
+    }
+}
+
+/// How one Foundation counts the scheduled courses eligible for it.
+enum FoundationReq<'a> {
+    /// One course each from one of the options, with how many that option needs. Only
+    /// [`ElectiveReq::SetOpts`] has more than one option.
+    Count(Vec<(Vec<&'a CourseCode>, u32)>),
+    /// By credits, from the courses worth any, largest first
+    Credits {
+        candidates: Vec<&'a CourseCode>,
+        need: u32,
+    },
+}
+
+/// How the search has decided to meet a Foundation.
+#[derive(Clone)]
+enum Commitment<'a> {
+    /// From this option, sharing courses with the other count-based Foundations through a max flow
+    Option(usize),
+    /// With this set of courses, none of which could be left out
+    Cover(Vec<&'a CourseCode>),
+}
+
+/// Search steps after which the assignment is reported as undetermined
+const MAX_STEPS: usize = 10_000;
+
+/// Unit-capacity max flow from count-based Foundations, each with the capacity it needs, to the
+/// courses eligible for them.
+struct FoundationFlow<'a> {
+    candidates: Vec<Vec<&'a CourseCode>>,
+    owner: HashMap<&'a CourseCode, usize>,
+    count: Vec<u32>,
+    need: Vec<u32>,
+}
+
+impl<'a> FoundationFlow<'a> {
+    /// Give `f` one more course along an augmenting path: each Foundation on it passes a course on to
+    /// the previous one and takes another, until a course nobody uses is reached.
+    fn augment(&mut self, f: usize, visited: &mut HashSet<&'a CourseCode>) -> bool {
+        for i in 0..self.candidates[f].len() {
+            let code = self.candidates[f][i];
+            if self.owner.get(code) == Some(&f) || !visited.insert(code) {
+                continue;
+            }
+            let freed = match self.owner.get(code) {
+                Some(&other) => self.augment(other, visited),
+                None => true,
+            };
+            if freed {
+                if let Some(other) = self.owner.insert(code, f) {
+                    self.count[other] -= 1;
+                }
+                self.count[f] += 1;
+                return true;
+            }
         }
+        false
+    }
+
+    fn fill(&mut self, f: usize) {
+        while self.count[f] < self.need[f] && self.augment(f, &mut HashSet::new()) {}
+    }
+}
+
+/// The courses, progress and need of each Foundation for one set of commitments.
+struct FoundationOutcome<'a> {
+    applied: Vec<Vec<&'a CourseCode>>,
+    progress: Vec<u32>,
+    need: Vec<u32>,
+}
+
+impl FoundationOutcome<'_> {
+    fn met(&self) -> Vec<bool> {
+        self.progress
+            .iter()
+            .zip(&self.need)
+            .map(|(p, n)| p >= n)
+            .collect()
     }
-    // Try all possible assignments of courses to foundation geneds (backtracking)
-    fn assign_foundations<'a>(
-        reqs: &[&'a ElectiveReq],
-        names: &[&'a String],
-        idx: usize,
-        used: &mut HashSet<&'a CourseCode>,
-        sched_courses: &HashSet<&'a CourseCode>,
-        catalog: &Catalog,
-        fail_foundation: &mut Option<String>,
-    ) -> bool {
-        if idx == reqs.len() {
-            return true;
-        }
-        if let Some(candidates) = satisfy_req(reqs[idx], sched_courses, catalog) {
-            let available: Vec<_> = candidates.difference(used).copied().collect();
-            if available.len() < candidates.len() {
-                *fail_foundation = Some(names[idx].clone());
-                return false;
+
+    fn met_count(&self) -> usize {
+        self.met().iter().filter(|m| **m).count()
+    }
+
+    /// More Foundations met is better, then those listed earlier in the catalog.
+    fn key(&self) -> (usize, Vec<bool>) {
+        let met = self.met();
+        (met.iter().filter(|m| **m).count(), met)
+    }
+}
+
+/// Branch and bound over which Foundations to meet, in catalog order and trying to meet each before
+/// leaving it unmet. A Foundation is met from one of its options or with one of its covering sets,
+/// and the commitments made so far must stay feasible together.
+struct FoundationSearch<'a, 'b> {
+    sched: &'b Schedule,
+    reqs: Vec<FoundationReq<'a>>,
+    commitments: Vec<Option<Commitment<'a>>>,
+    /// The courses of the covering sets committed to
+    reserved: HashSet<&'a CourseCode>,
+    best: Option<FoundationOutcome<'a>>,
+    steps: usize,
+    undetermined: bool,
+}
+
+impl<'a> FoundationSearch<'a, '_> {
+    /// Share the courses outside the covering sets among the count-based Foundations: first those
+    /// committed to, each from its option, then with `rest` the others, each from the option with
+    /// the least missing.
+    fn share(&self, rest: bool) -> FoundationFlow<'a> {
+        let n = self.reqs.len();
+        let available = |codes: &[&'a CourseCode]| -> Vec<&'a CourseCode> {
+            codes
+                .iter()
+                .filter(|c| !self.reserved.contains(*c))
+                .copied()
+                .collect()
+        };
+        let mut flow = FoundationFlow {
+            candidates: vec![Vec::new(); n],
+            owner: HashMap::new(),
+            count: vec![0; n],
+            need: vec![0; n],
+        };
+        let mut others = Vec::new();
+        for (f, req) in self.reqs.iter().enumerate() {
+            let FoundationReq::Count(options) = req else {
+                continue;
+            };
+            let (codes, need) = match self.commitments[f] {
+                Some(Commitment::Option(pick)) => &options[pick],
+                _ if rest => {
+                    others.push(f);
+                    options
+                        .iter()
+                        .min_by_key(|(codes, need)| {
+                            need.saturating_sub(available(codes).len() as u32)
+                        })
+                        .expect("count-based Foundations have at least one option")
+                }
+                _ => continue,
+            };
+            flow.candidates[f] = available(codes);
+            flow.need[f] = *need;
+        }
+        for f in 0..n {
+            if !others.contains(&f) {
+                flow.fill(f);
+            }
+        }
+        // Augmenting paths keep the count of every other Foundation, so those committed to stay met
+        for f in others {
+            flow.fill(f);
+        }
+        flow
+    }
+
+    /// Whether every Foundation committed to can still be met together.
+    fn feasible(&self) -> bool {
+        let flow = self.share(false);
+        flow.count.iter().zip(&flow.need).all(|(c, n)| c >= n)
+    }
+
+    /// Count the courses towards the Foundations committed to, then give what is left to the others.
+    fn evaluate(&self) -> FoundationOutcome<'a> {
+        let n = self.reqs.len();
+        let flow = self.share(true);
+        let mut need = flow.need;
+        let mut applied: Vec<Vec<&CourseCode>> = vec![Vec::new(); n];
+        let mut taken = self.reserved.clone();
+        for (f, commitment) in self.commitments.iter().enumerate() {
+            if let Some(Commitment::Cover(cover)) = commitment {
+                applied[f] = cover.clone();
             }
-            // Try all combinations of candidates from available (order doesn't matter)
-            fn combinations<T: Clone>(
-                data: &[T],
-                k: usize,
-                out: &mut Vec<T>,
-                res: &mut Vec<Vec<T>>,
-            ) {
-                if out.len() == k {
-                    res.push(out.clone());
-                    return;
+        }
+        for (code, f) in flow.owner {
+            applied[f].push(code);
+            taken.insert(code);
+        }
+        for (f, req) in self.reqs.iter().enumerate() {
+            let FoundationReq::Credits {
+                candidates,
+                need: credits,
+            } = req
+            else {
+                continue;
+            };
+            need[f] = *credits;
+            if self.commitments[f].is_none() {
+                let mut total = 0;
+                for code in candidates {
+                    if total >= *credits {
+                        break;
+                    }
+                    if taken.insert(code) {
+                        applied[f].push(code);
+                        total += self.sched.course_credits(code);
+                    }
                 }
-                for i in 0..data.len() {
-                    out.push(data[i].clone());
-                    let rest = &data[i + 1..];
-                    combinations(rest, k, out, res);
-                    out.pop();
+            }
+        }
+        let progress = applied
+            .iter()
+            .zip(&self.reqs)
+            .map(|(codes, req)| match req {
+                FoundationReq::Count(_) => codes.len() as u32,
+                FoundationReq::Credits { .. } => {
+                    codes.iter().map(|c| self.sched.course_credits(c)).sum()
                 }
+            })
+            .collect();
+        FoundationOutcome {
+            applied,
+            progress,
+            need,
+        }
+    }
+
+    /// Counts a step, or reports that the search is over: every Foundation is met, or it has run out
+    /// of steps.
+    fn step(&mut self) -> bool {
+        let n = self.reqs.len();
+        if self.best.as_ref().is_some_and(|best| best.met_count() == n) {
+            return false;
+        }
+        if self.steps >= MAX_STEPS {
+            self.undetermined = true;
+            return false;
+        }
+        self.steps += 1;
+        true
+    }
+
+    fn search(&mut self, f: usize) {
+        let n = self.reqs.len();
+        if !self.step() {
+            return;
+        }
+        if f == n {
+            let outcome = self.evaluate();
+            if self
+                .best
+                .as_ref()
+                .is_none_or(|best| outcome.key() > best.key())
+            {
+                self.best = Some(outcome);
             }
-            let mut combs = Vec::new();
-            let mut out = Vec::new();
-            combinations(&available, candidates.len(), &mut out, &mut combs);
-            for comb in &combs {
-                let mut new_used = used.clone();
-                new_used.extend(comb.iter().copied());
-                if assign_foundations(
-                    reqs,
-                    names,
-                    idx + 1,
-                    &mut new_used,
-                    sched_courses,
-                    catalog,
-                    fail_foundation,
-                ) {
-                    return true;
+            return;
+        }
+        // Nothing below can beat the best so far if even meeting every Foundation left would not
+        let mut bound: Vec<bool> = self.commitments[..f].iter().map(Option::is_some).collect();
+        bound.resize(n, true);
+        let bound = (bound.iter().filter(|m| **m).count(), bound);
+        if self.best.as_ref().is_some_and(|best| bound <= best.key()) {
+            return;
+        }
+        match &self.reqs[f] {
+            FoundationReq::Count(options) => {
+                for pick in 0..options.len() {
+                    self.commitments[f] = Some(Commitment::Option(pick));
+                    if self.feasible() {
+                        self.search(f + 1);
+                    }
                 }
             }
-            *fail_foundation = Some(names[idx].clone());
-            false
-        } else {
-            *fail_foundation = Some(names[idx].clone());
-            false
+            FoundationReq::Credits { candidates, need } => {
+                let candidates: Vec<_> = candidates
+                    .iter()
+                    .filter(|c| !self.reserved.contains(*c))
+                    .copied()
+                    .collect();
+                let need = *need;
+                let remaining = candidates
+                    .iter()
+                    .map(|c| self.sched.course_credits(c))
+                    .sum();
+                self.cover(f, &candidates, need, remaining, &mut Vec::new(), 0);
+            }
         }
+        self.commitments[f] = None;
+        self.search(f + 1);
     }
-    if !foundation_reqs.is_empty() {
-        let mut used = HashSet::new();
-        let mut fail_foundation = None;
-        let req_refs: Vec<&ElectiveReq> = foundation_reqs.to_vec();
-        let name_refs: Vec<&String> = foundation_names.to_vec();
-        if !assign_foundations(
-            &req_refs,
-            &name_refs,
-            0,
-            &mut used,
-            &sched_courses,
-            &sched.catalog,
-            &mut fail_foundation,
-        ) {
-            return Ok(false);
+
+    /// Commit `f` to each set that adds some of `candidates` to `set` to reach `need` credits and
+    /// needs every one of its courses, and search on from each. `remaining` is what `candidates` are
+    /// worth together.
+    fn cover(
+        &mut self,
+        f: usize,
+        candidates: &[&'a CourseCode],
+        need: u32,
+        remaining: u32,
+        set: &mut Vec<&'a CourseCode>,
+        total: u32,
+    ) {
+        if total + remaining < need || !self.step() {
+            return;
         }
+        if total >= need {
+            if set
+                .iter()
+                .all(|c| total - self.sched.course_credits(c) < need)
+            {
+                self.reserved.extend(set.iter());
+                self.commitments[f] = Some(Commitment::Cover(set.clone()));
+                if self.feasible() {
+                    self.search(f + 1);
+                }
+                for code in set.iter() {
+                    self.reserved.remove(code);
+                }
+            }
+            return;
+        }
+        let Some((&code, rest)) = candidates.split_first() else {
+            return;
+        };
+        let credits = self.sched.course_credits(code);
+        set.push(code);
+        self.cover(f, rest, need, remaining - credits, set, total + credits);
+        set.pop();
+        self.cover(f, rest, need, remaining - credits, set, total);
     }
+}
 
-    // 3. Skills & Perspectives: each must be satisfied, but no course can be used for more than 3 S&Ps
-    let mut sp_reqs = vec![];
-    let mut sp_names = vec![];
-    for gened in sched.catalog.geneds.iter() {
-        if let GenEd::SkillAndPerspective { req, name } = gened {
-            sp_reqs.push(req);
-            sp_names.push(name);
+/// The scheduled courses among `codes`, without repeats; counting by credits, only those worth any.
+fn eligible<'a>(
+    sched: &Schedule,
+    sched_courses: &HashSet<&CourseCode>,
+    codes: &'a [CourseCode],
+    by_credits: bool,
+) -> Vec<&'a CourseCode> {
+    let mut candidates: Vec<&CourseCode> = Vec::new();
+    for code in codes {
+        if sched_courses.contains(code)
+            && !candidates.contains(&code)
+            && (!by_credits || sched.course_credits(code) > 0)
+        {
+            candidates.push(code);
         }
     }
-    let mut all_sp_ok = true;
-    let mut sp_course_counts: HashMap<&CourseCode, usize> = HashMap::new();
-    for req in sp_reqs.iter() {
-        if let Some(courses) = satisfy_req(req, &sched_courses, &sched.catalog) {
-            for c in courses {
-                *sp_course_counts.entry(c).or_insert(0) += 1;
+    candidates
+}
+
+/// Assign the schedule's courses to the Foundation GenEds, counting each course towards at most one
+/// Foundation, so that as many Foundations as possible are met and, among those ways, the ones listed
+/// earlier in the catalog. The courses left over then count towards the unmet Foundations.
+///
+/// Which Foundations to meet is found by branch and bound: count-based Foundations share courses
+/// through a max flow, and credit-based ones are given each set of courses that covers them in turn.
+/// If that takes more than `MAX_STEPS` steps the search stops, and the assignment is the best found
+/// so far, marked [`FoundationAssignment::undetermined`].
+pub fn foundation_assignment(sched: &Schedule) -> FoundationAssignment<'_> {
+    let sched_courses: HashSet<&CourseCode> = std::iter::once(&sched.incoming)
+        .chain(sched.courses.iter())
+        .flatten()
+        .collect();
+    let foundations: Vec<_> = sched
+        .catalog
+        .geneds
+        .iter()
+        .filter_map(|gened| match gened {
+            GenEd::Foundation { name, req } => Some((name.as_str(), req)),
+            _ => None,
+        })
+        .collect();
+
+    let scheduled = |codes, by_credits| eligible(sched, &sched_courses, codes, by_credits);
+    let reqs: Vec<_> = foundations
+        .iter()
+        .map(|(_, req)| match req {
+            ElectiveReq::Set(codes) => {
+                FoundationReq::Count(vec![(scheduled(codes, false), codes.len() as u32)])
+            }
+            ElectiveReq::SetOpts(opts) if opts.is_empty() => {
+                FoundationReq::Count(vec![(Vec::new(), 0)])
+            }
+            ElectiveReq::SetOpts(opts) => FoundationReq::Count(
+                opts.iter()
+                    .map(|opt| (scheduled(opt, false), opt.len() as u32))
+                    .collect(),
+            ),
+            ElectiveReq::Courses { num, courses } => {
+                FoundationReq::Count(vec![(scheduled(courses, false), *num as u32)])
+            }
+            ElectiveReq::Credits { num, courses } => {
+                let mut candidates = scheduled(courses, true);
+                // Larger courses first, so fewer courses are spent on each Foundation
+                candidates.sort_by_key(|c| std::cmp::Reverse(sched.course_credits(c)));
+                FoundationReq::Credits {
+                    candidates,
+                    need: *num,
+                }
+            }
+        })
+        .collect();
+
+    let mut search = FoundationSearch {
+        sched,
+        commitments: vec![None; reqs.len()],
+        reqs,
+        reserved: HashSet::new(),
+        best: None,
+        steps: 0,
+        undetermined: false,
+    };
+    search.search(0);
+    // Out of steps before any leaf, commit to nothing
+    let outcome = match search.best.take() {
+        Some(best) => best,
+        None => search.evaluate(),
+    };
+
+    let foundations = foundations
+        .into_iter()
+        .enumerate()
+        .map(|(f, (name, req))| {
+            let all_codes = req.all_course_codes();
+            let mut applied: Vec<CourseCode> =
+                outcome.applied[f].iter().map(|c| (*c).clone()).collect();
+            applied.sort_by_key(|code| all_codes.iter().position(|c| c == code));
+            let missing = outcome.need[f].saturating_sub(outcome.progress[f]);
+            let shortfall = match req {
+                _ if missing == 0 => None,
+                ElectiveReq::Credits { .. } => Some(Shortfall::Credits(missing)),
+                _ => Some(Shortfall::Courses(missing as usize)),
+            };
+            FoundationMatch {
+                name,
+                req,
+                applied,
+                shortfall,
             }
-        } else {
-            all_sp_ok = false;
+        })
+        .collect();
+    FoundationAssignment {
+        foundations,
+        undetermined: search.undetermined,
+    }
+}
+
+/// How far a schedule is towards one GenEd.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GenEdProgress<'a> {
+    pub gened: &'a GenEd,
+    /// Scheduled courses counted towards the GenEd
+    pub applied: Vec<CourseCode>,
+    pub shortfall: Option<Shortfall>,
+    /// Why the GenEd is unmet beyond the shortfall, e.g. courses it shares with other GenEds
+    pub conflicts: Vec<String>,
+}
+
+impl GenEdProgress<'_> {
+    pub fn is_met(&self) -> bool {
+        self.shortfall.is_none() && self.conflicts.is_empty()
+    }
+}
+
+/// Progress towards every Core, Foundation and Skills & Perspectives GenEd in the catalog, in that order.
+pub fn gened_progress(sched: &Schedule) -> Vec<GenEdProgress<'_>> {
+    gened_progress_with(sched, foundation_assignment(sched))
+}
+
+fn gened_progress_with<'a>(
+    sched: &'a Schedule,
+    assignment: FoundationAssignment<'a>,
+) -> Vec<GenEdProgress<'a>> {
+    let sched_courses: HashSet<&CourseCode> = std::iter::once(&sched.incoming)
+        .chain(sched.courses.iter())
+        .flatten()
+        .collect();
+    let progress = |gened| {
+        let (applied, shortfall) = req_progress(gened_req(gened), &sched_courses, sched);
+        GenEdProgress {
+            gened,
+            applied,
+            shortfall,
+            conflicts: Vec::new(),
+        }
+    };
+    let mut entries = Vec::new();
+
+    // 1. Core: each must be satisfied, overlap allowed
+    for gened in sched.catalog.geneds.iter() {
+        if let GenEd::Core { .. } = gened {
+            entries.push(progress(gened));
         }
     }
-    if !all_sp_ok {
-        return Ok(false);
+
+    // 2. Foundation: each must be satisfied, but no course can be used for more than one Foundation
+    let foundations = sched
+        .catalog
+        .geneds
+        .iter()
+        .filter(|gened| matches!(gened, GenEd::Foundation { .. }));
+    for (gened, foundation) in foundations.zip(assignment.foundations) {
+        let mut conflicts = Vec::new();
+        if foundation.shortfall.is_some()
+            && req_progress(foundation.req, &sched_courses, sched)
+                .1
+                .is_none()
+        {
+            conflicts
+                .push("its courses are already counted towards another Foundation".to_string());
+        }
+        if foundation.shortfall.is_some() && assignment.undetermined {
+            conflicts.push(
+                "the search for a course assignment ran out of steps before finding whether it can be met"
+                    .to_string(),
+            );
+        }
+        entries.push(GenEdProgress {
+            gened,
+            applied: foundation.applied,
+            shortfall: foundation.shortfall,
+            conflicts,
+        });
+    }
+
+    // 3. Skills & Perspectives: each must be satisfied, but no course can be used for more than 3 S&Ps
+    let mut sp_entries = Vec::new();
+    let mut sp_course_counts: HashMap<CourseCode, usize> = HashMap::new();
+    for gened in sched.catalog.geneds.iter() {
+        if let GenEd::SkillAndPerspective { req, .. } = gened {
+            if let Some(courses) = satisfy_req(req, &sched_courses, sched) {
+                for c in courses {
+                    *sp_course_counts.entry(c.clone()).or_insert(0) += 1;
+                }
+            }
+            sp_entries.push(progress(gened));
+        }
     }
     // No course can be used for more than 3 S&Ps
-    let mut sp_overlap_fail = false;
-    for count in sp_course_counts.values() {
-        if *count > 3 {
-            sp_overlap_fail = true;
+    for entry in sp_entries.iter_mut() {
+        let mut overused: Vec<_> = entry
+            .applied
+            .iter()
+            .filter(|c| sp_course_counts.get(*c).is_some_and(|count| *count > 3))
+            .cloned()
+            .collect();
+        overused.sort_by_key(|c| c.to_string());
+        for c in overused {
+            entry.conflicts.push(format!(
+                "{c} counts towards more than 3 Skills & Perspectives"
+            ));
         }
     }
-    if sp_overlap_fail {
-        return Ok(false);
+    entries.extend(sp_entries);
+
+    entries
+}
+
+fn gened_req(gened: &GenEd) -> &ElectiveReq {
+    match gened {
+        GenEd::Core { req, .. }
+        | GenEd::Foundation { req, .. }
+        | GenEd::SkillAndPerspective { req, .. } => req,
+    }
+}
+
+pub fn are_geneds_satisfied(sched: &Schedule) -> Result<bool> {
+    let assignment = foundation_assignment(sched);
+    if assignment.undetermined {
+        let unmet: Vec<_> = assignment
+            .foundations
+            .iter()
+            .filter(|f| f.shortfall.is_some())
+            .map(|f| f.name)
+            .collect();
+        bail!(
+            "Could not determine whether the Foundation GenEds can be met: the search stopped after {MAX_STEPS} steps with {} unmet",
+            unmet.join(", ")
+        );
+    }
+    Ok(gened_progress_with(sched, assignment)
+        .iter()
+        .all(|p| p.is_met()))
+}
+
+impl Schedule {
+    /// Which scheduled course counts towards which Foundation GenEd.
+    pub fn foundation_assignment(&self) -> FoundationAssignment<'_> {
+        foundation_assignment(self)
     }
-    Ok(true)
 }
 
 // Used for script_assistant crate
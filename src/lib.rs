@@ -1,6 +1,12 @@
+pub mod audit;
+pub mod catalog_diff;
+pub mod critical_path;
 pub mod geneds;
+pub mod lint;
 pub mod load_catalogs;
 pub mod model;
+pub mod prereq_graph;
+pub mod prereq_parser;
 pub mod prereqs;
 pub mod read_excel_file;
 pub mod schedule;
@@ -0,0 +1,426 @@
+//! Consistency checks over catalog data, reported as typed findings.
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::geneds::{ElectiveReq, GenEd};
+use crate::prereqs::CourseReq;
+use crate::schedule::{Catalog, CourseCode, CourseCodeSuffix, CourseTermOffering};
+
+/// Where a course code was referenced from.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum CodeSource {
+    Program(String),
+    ProgramElective {
+        program: String,
+        elective: String,
+    },
+    GenEd(String),
+    Prereqs,
+    /// The cross-listings of a catalog course
+    CrossListing(CourseCode),
+}
+
+impl fmt::Display for CodeSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodeSource::Program(name) => write!(f, "program {name}"),
+            CodeSource::ProgramElective { program, elective } => {
+                write!(f, "elective group {elective} of program {program}")
+            }
+            CodeSource::GenEd(name) => write!(f, "GenEd {name}"),
+            CodeSource::Prereqs => write!(f, "the prerequisite list"),
+            CodeSource::CrossListing(code) => write!(f, "the cross-listings of {code}"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum LintFinding {
+    /// `course` has a prerequisite on `missing`, which has no catalog entry.
+    DanglingPrereq {
+        course: CourseCode,
+        missing: CourseCode,
+    },
+    /// Courses whose prerequisites depend on each other with at least one strict (prior-term) link,
+    /// so none of them can ever be scheduled.
+    PrereqCycle { courses: Vec<CourseCode> },
+    /// A catalog course without a credit value; it counts as 0 credits everywhere.
+    MissingCredits { course: CourseCode },
+    /// A course referenced somewhere in the catalog without a catalog entry of its own.
+    UnknownCourse {
+        course: CourseCode,
+        source: CodeSource,
+    },
+    /// `course` co-requires its lab/lecture partner `partner`, but not the other way around.
+    AsymmetricCorequisite {
+        course: CourseCode,
+        partner: CourseCode,
+    },
+    /// A lecture and its lab co-require each other "or concurrently"; they most likely have to be
+    /// taken in the same term ([`CourseReq::SameTermCourse`]).
+    SameTermCandidate {
+        lecture: CourseCode,
+        lab: CourseCode,
+    },
+    /// `course` is cross-listed with `other`, but `other` does not list `course` back.
+    OneWayCrossListing {
+        course: CourseCode,
+        other: CourseCode,
+    },
+    /// A course only offered in the summer placed in a regular semester of a program.
+    SummerCourseInProgram {
+        program: String,
+        semester: usize,
+        course: CourseCode,
+    },
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintFinding::DanglingPrereq { course, missing } => write!(
+                f,
+                "{course} has a prerequisite on {missing}, which is not in the catalog"
+            ),
+            LintFinding::PrereqCycle { courses } => write!(
+                f,
+                "prerequisite cycle between {}",
+                courses
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            LintFinding::MissingCredits { course } => {
+                write!(f, "{course} has no credit value and counts as 0 credits")
+            }
+            LintFinding::UnknownCourse { course, source } => {
+                write!(f, "{course} is used by {source} but is not in the catalog")
+            }
+            LintFinding::AsymmetricCorequisite { course, partner } => write!(
+                f,
+                "{course} co-requires its lab/lecture partner {partner}, but {partner} does not co-require {course}"
+            ),
+            LintFinding::SameTermCandidate { lecture, lab } => write!(
+                f,
+                "{lecture} and its lab {lab} co-require each other \"or concurrently\"; they are likely meant to be taken in the same term"
+            ),
+            LintFinding::OneWayCrossListing { course, other } => write!(
+                f,
+                "{course} is cross-listed with {other}, but {other} does not list {course}"
+            ),
+            LintFinding::SummerCourseInProgram {
+                program,
+                semester,
+                course,
+            } => write!(
+                f,
+                "{course} is only offered in the summer but is placed in semester {} of {program}",
+                semester + 1
+            ),
+        }
+    }
+}
+
+/// How a course depends on a course in its requirement tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Link {
+    /// Taken in an earlier term
+    Strict,
+    /// Taken in an earlier or the same term
+    Concurrent,
+    SameTerm,
+}
+
+// Prereq edges from a course to the codes in its requirement tree
+fn prereq_edges(req: &CourseReq, edges: &mut Vec<(CourseCode, Link)>) {
+    match req {
+        CourseReq::And(reqs) | CourseReq::Or(reqs) => {
+            for r in reqs {
+                prereq_edges(r, edges);
+            }
+        }
+        CourseReq::PreCourse(code) | CourseReq::PreCourseGrade(code, _) => {
+            edges.push((code.clone(), Link::Strict))
+        }
+        CourseReq::CoCourse(code) | CourseReq::CoCourseGrade(code, _) => {
+            edges.push((code.clone(), Link::Concurrent))
+        }
+        CourseReq::SameTermCourse(code) => edges.push((code.clone(), Link::SameTerm)),
+        CourseReq::Program(_)
+        | CourseReq::Standing(_)
+        | CourseReq::CreditsEarned(_)
+        | CourseReq::TestScore(..)
+        | CourseReq::Instructor
+        | CourseReq::NotRequired => {}
+    }
+}
+
+fn sorted_codes<'a>(codes: impl IntoIterator<Item = &'a CourseCode>) -> Vec<&'a CourseCode> {
+    let mut codes: Vec<_> = codes.into_iter().collect();
+    codes.sort_by_key(|c| c.to_string());
+    codes.dedup();
+    codes
+}
+
+// Tarjan's strongly connected components over the prereq graph
+fn strongly_connected<'a>(
+    graph: &HashMap<&'a CourseCode, Vec<(CourseCode, Link)>>,
+) -> Vec<Vec<&'a CourseCode>> {
+    struct State<'a> {
+        index: usize,
+        indices: HashMap<&'a CourseCode, usize>,
+        lowlink: HashMap<&'a CourseCode, usize>,
+        stack: Vec<&'a CourseCode>,
+        on_stack: HashSet<&'a CourseCode>,
+        components: Vec<Vec<&'a CourseCode>>,
+    }
+
+    fn visit<'a>(
+        node: &'a CourseCode,
+        graph: &HashMap<&'a CourseCode, Vec<(CourseCode, Link)>>,
+        state: &mut State<'a>,
+    ) {
+        state.indices.insert(node, state.index);
+        state.lowlink.insert(node, state.index);
+        state.index += 1;
+        state.stack.push(node);
+        state.on_stack.insert(node);
+
+        for (next, _) in graph.get(node).into_iter().flatten() {
+            // Only nodes that have prereqs of their own can be part of a cycle
+            let Some((&next, _)) = graph.get_key_value(next) else {
+                continue;
+            };
+            if !state.indices.contains_key(next) {
+                visit(next, graph, state);
+                let low = state.lowlink[node].min(state.lowlink[next]);
+                state.lowlink.insert(node, low);
+            } else if state.on_stack.contains(next) {
+                let low = state.lowlink[node].min(state.indices[next]);
+                state.lowlink.insert(node, low);
+            }
+        }
+
+        if state.lowlink[node] == state.indices[node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack.remove(member);
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            state.components.push(component);
+        }
+    }
+
+    let mut state = State {
+        index: 0,
+        indices: HashMap::new(),
+        lowlink: HashMap::new(),
+        stack: Vec::new(),
+        on_stack: HashSet::new(),
+        components: Vec::new(),
+    };
+    for node in sorted_codes(graph.keys().copied()) {
+        if !state.indices.contains_key(node) {
+            visit(node, graph, &mut state);
+        }
+    }
+    state.components
+}
+
+/// The lecture a lab belongs to: labs are numbered one above their lecture.
+fn lab_partner(catalog: &Catalog, code: &CourseCode) -> Option<CourseCode> {
+    let CourseCodeSuffix::Number(num) = code.code else {
+        return None;
+    };
+    if !catalog.courses.get(code)?.lab || num == 0 {
+        return None;
+    }
+    let lecture = CourseCode {
+        stem: code.stem.clone(),
+        code: CourseCodeSuffix::Number(num - 1),
+    };
+    catalog.courses.contains_key(&lecture).then_some(lecture)
+}
+
+impl Catalog {
+    /// Check the catalog data for common mistakes. An empty result means nothing suspicious was found.
+    pub fn lint(&self) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+
+        let graph: HashMap<&CourseCode, Vec<(CourseCode, Link)>> = self
+            .prereqs
+            .iter()
+            .map(|(code, req)| {
+                let mut edges = Vec::new();
+                prereq_edges(req, &mut edges);
+                (code, edges)
+            })
+            .collect();
+
+        // Prerequisites on courses that do not exist
+        for course in sorted_codes(self.prereqs.keys()) {
+            if !self.courses.contains_key(course) {
+                findings.push(LintFinding::UnknownCourse {
+                    course: course.clone(),
+                    source: CodeSource::Prereqs,
+                });
+            }
+            for (missing, _) in &graph[course] {
+                if !self.courses.contains_key(missing) {
+                    findings.push(LintFinding::DanglingPrereq {
+                        course: course.clone(),
+                        missing: missing.clone(),
+                    });
+                }
+            }
+        }
+
+        // Cycles: a group of mutually dependent courses is only a problem if one of the links is strict
+        for component in strongly_connected(&graph) {
+            let members: HashSet<&CourseCode> = component.iter().copied().collect();
+            let has_strict_link = component.iter().any(|code| {
+                graph[code]
+                    .iter()
+                    .any(|(next, link)| *link == Link::Strict && members.contains(next))
+            });
+            if has_strict_link {
+                findings.push(LintFinding::PrereqCycle {
+                    courses: sorted_codes(component).into_iter().cloned().collect(),
+                });
+            }
+        }
+
+        for course in sorted_codes(self.courses.keys()) {
+            if self.courses[course].credits.is_none() {
+                findings.push(LintFinding::MissingCredits {
+                    course: course.clone(),
+                });
+            }
+        }
+
+        // Programs, electives and GenEds referring to courses that do not exist
+        let mut check_known = |codes: Vec<&CourseCode>, source: CodeSource| {
+            for course in sorted_codes(codes) {
+                if !self.courses.contains_key(course) {
+                    findings.push(LintFinding::UnknownCourse {
+                        course: course.clone(),
+                        source: source.clone(),
+                    });
+                }
+            }
+        };
+        for prog in &self.programs {
+            check_known(
+                prog.semesters.iter().flatten().collect(),
+                CodeSource::Program(prog.name.clone()),
+            );
+            for elective in &prog.electives {
+                check_known(
+                    elective_codes(&elective.req),
+                    CodeSource::ProgramElective {
+                        program: prog.name.clone(),
+                        elective: elective.name.clone(),
+                    },
+                );
+            }
+        }
+        for gened in &self.geneds {
+            let (GenEd::Core { name, req }
+            | GenEd::Foundation { name, req }
+            | GenEd::SkillAndPerspective { name, req }) = gened;
+            check_known(elective_codes(req), CodeSource::GenEd(name.clone()));
+        }
+        for course in sorted_codes(self.courses.keys()) {
+            check_known(
+                self.courses[course].cross_listed.iter().collect(),
+                CodeSource::CrossListing(course.clone()),
+            );
+        }
+
+        // Cross-listings must name each other
+        for course in sorted_codes(self.courses.keys()) {
+            for other in sorted_codes(&self.courses[course].cross_listed) {
+                if self
+                    .courses
+                    .get(other)
+                    .is_some_and(|info| !info.cross_listed.contains(course))
+                {
+                    findings.push(LintFinding::OneWayCrossListing {
+                        course: course.clone(),
+                        other: other.clone(),
+                    });
+                }
+            }
+        }
+
+        // Lab/lecture pairs must co-require each other, or not at all, and most likely in the same term
+        let link = |from: &CourseCode, to: &CourseCode| {
+            graph
+                .get(from)
+                .and_then(|edges| edges.iter().find(|(code, _)| code == to))
+                .map(|&(_, link)| link)
+        };
+        let co_requires =
+            |from: &CourseCode, to: &CourseCode| link(from, to).is_some_and(|l| l != Link::Strict);
+        for lab in sorted_codes(self.courses.keys()) {
+            let Some(lecture) = lab_partner(self, lab) else {
+                continue;
+            };
+            match (co_requires(&lecture, lab), co_requires(lab, &lecture)) {
+                (true, false) => findings.push(LintFinding::AsymmetricCorequisite {
+                    course: lecture.clone(),
+                    partner: lab.clone(),
+                }),
+                (false, true) => findings.push(LintFinding::AsymmetricCorequisite {
+                    course: lab.clone(),
+                    partner: lecture.clone(),
+                }),
+                (true, true)
+                    if link(&lecture, lab) == Some(Link::Concurrent)
+                        || link(lab, &lecture) == Some(Link::Concurrent) =>
+                {
+                    findings.push(LintFinding::SameTermCandidate {
+                        lecture: lecture.clone(),
+                        lab: lab.clone(),
+                    })
+                }
+                _ => {}
+            }
+        }
+
+        // Summer-only courses cannot be taken in the Fall/Spring semesters programs are laid out in
+        for prog in &self.programs {
+            for (semester, sem) in prog.semesters.iter().enumerate() {
+                for course in sem {
+                    if self
+                        .courses
+                        .get(course)
+                        .is_some_and(|info| info.offering == CourseTermOffering::Summer)
+                    {
+                        findings.push(LintFinding::SummerCourseInProgram {
+                            program: prog.name.clone(),
+                            semester,
+                            course: course.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        findings
+    }
+}
+
+fn elective_codes(req: &ElectiveReq) -> Vec<&CourseCode> {
+    match req {
+        ElectiveReq::Set(codes)
+        | ElectiveReq::Courses { courses: codes, .. }
+        | ElectiveReq::Credits { courses: codes, .. } => codes.iter().collect(),
+        ElectiveReq::SetOpts(opts) => opts.iter().flatten().collect(),
+    }
+}
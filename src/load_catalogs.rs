@@ -1,4 +1,11 @@
+use anyhow::{Context, Result, bail};
 use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
 
 #[path = "../resources/course_reqs/mod.rs"]
 mod course_reqs;
@@ -12,14 +19,397 @@ mod courses;
 #[path = "../resources/general_education.rs"]
 mod general_education;
 
-use crate::schedule::Catalog;
+use crate::geneds::GenEd;
+use crate::prereqs::CourseReq;
+use crate::schedule::{
+    Catalog, CourseCode, CourseInfo, Program, Schedule, Semester, Term, generate_schedule,
+};
+
+/// Environment variable pointing at catalog data to load in addition to the compiled-in catalog.
+/// It may name a single catalog directory or a directory holding one sub-directory per catalog year.
+pub const CATALOG_DIR_ENV: &str = "ROSS_CATALOG_DIR";
 
 lazy_static! {
-    pub static ref CATALOGS: Vec<Catalog> = vec![Catalog {
+    pub static ref CATALOGS: CatalogRegistry = load_catalogs();
+}
+
+/// All known catalog years, kept sorted by `low_year` with at most one catalog per year.
+#[derive(Debug, Clone, Default)]
+pub struct CatalogRegistry {
+    catalogs: Vec<Catalog>,
+    load_errors: Vec<CatalogLoadError>,
+}
+
+/// A catalog directory that could not be loaded. The registry keeps the catalogs it already had.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CatalogLoadError {
+    pub dir: PathBuf,
+    pub message: String,
+}
+
+impl fmt::Display for CatalogLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Could not load catalogs from {}: {}",
+            self.dir.display(),
+            self.message
+        )
+    }
+}
+
+impl std::error::Error for CatalogLoadError {}
+
+impl CatalogRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a catalog, replacing (and returning) any catalog already registered for the same year.
+    pub fn insert(&mut self, catalog: Catalog) -> Option<Catalog> {
+        match self.catalogs.binary_search(&catalog) {
+            Ok(idx) => Some(std::mem::replace(&mut self.catalogs[idx], catalog)),
+            Err(idx) => {
+                self.catalogs.insert(idx, catalog);
+                None
+            }
+        }
+    }
+
+    /// The catalog whose academic year starts in `low_year`, if there is one.
+    pub fn for_year(&self, low_year: u32) -> Option<&Catalog> {
+        self.catalogs.iter().find(|c| c.low_year == low_year)
+    }
+
+    /// The catalog in force for a student who matriculated in `entry_year`: the most recent catalog
+    /// published no later than that year. Returns `None` if every catalog is newer than `entry_year`.
+    pub fn catalog_for_entry_year(&self, entry_year: u32) -> Option<&Catalog> {
+        self.catalogs
+            .iter()
+            .rev()
+            .find(|c| c.low_year <= entry_year)
+    }
+
+    pub fn latest(&self) -> Option<&Catalog> {
+        self.catalogs.last()
+    }
+
+    pub fn years(&self) -> impl Iterator<Item = u32> + '_ {
+        self.catalogs.iter().map(|c| c.low_year)
+    }
+
+    /// Add the catalogs stored in `dir` (see [`load_catalog_dirs`]), replacing those for the same
+    /// years. If any of them is malformed none are added, and the error is also kept in
+    /// [`Self::load_errors`].
+    pub fn load_dir(&mut self, dir: &Path) -> Result<(), CatalogLoadError> {
+        match load_catalog_dirs(dir) {
+            Ok(catalogs) => {
+                for catalog in catalogs {
+                    self.insert(catalog);
+                }
+                Ok(())
+            }
+            Err(e) => {
+                let err = CatalogLoadError {
+                    dir: dir.to_path_buf(),
+                    message: format!("{e:#}"),
+                };
+                self.load_errors.push(err.clone());
+                Err(err)
+            }
+        }
+    }
+
+    /// Catalog directories that failed to load, e.g. the one named by [`CATALOG_DIR_ENV`]. Callers
+    /// should show these to the user, since the registry falls back to the other catalogs.
+    pub fn load_errors(&self) -> &[CatalogLoadError] {
+        &self.load_errors
+    }
+
+    /// Generate a schedule starting in `start`, using the catalog a student matriculating then must follow.
+    pub fn generate_schedule(
+        &self,
+        programs: Vec<&str>,
+        start: Term,
+        incoming: Option<Semester>,
+    ) -> Result<Schedule> {
+        let entry_year = start.academic_year();
+        let catalog = self
+            .catalog_for_entry_year(entry_year)
+            .ok_or_else(|| anyhow::anyhow!("No catalog in force for entry year {entry_year}"))?;
+        let mut sched = generate_schedule(programs, catalog.clone(), incoming)?;
+        sched.start = start;
+        Ok(sched)
+    }
+}
+
+impl Deref for CatalogRegistry {
+    type Target = [Catalog];
+
+    fn deref(&self) -> &Self::Target {
+        &self.catalogs
+    }
+}
+
+impl FromIterator<Catalog> for CatalogRegistry {
+    fn from_iter<I: IntoIterator<Item = Catalog>>(iter: I) -> Self {
+        let mut registry = Self::new();
+        for catalog in iter {
+            registry.insert(catalog);
+        }
+        registry
+    }
+}
+
+/// The catalog compiled into the binary from `resources/`.
+pub fn builtin_catalog() -> Catalog {
+    Catalog {
         geneds: general_education::geneds(),
         programs: programs::programs(),
         prereqs: course_reqs::prereqs(),
         courses: courses::courses(),
         low_year: 2025,
-    }];
+    }
+}
+
+// Catalogs loaded from disk take precedence over the compiled-in catalog for the same year
+fn load_catalogs() -> CatalogRegistry {
+    let mut registry = CatalogRegistry::from_iter([builtin_catalog()]);
+    if let Some(dir) = std::env::var_os(CATALOG_DIR_ENV)
+        && let Err(e) = registry.load_dir(Path::new(&dir))
+    {
+        eprintln!("{e}; falling back to the compiled-in catalog");
+    }
+    registry
+}
+
+/// Load either the single catalog stored in `dir`, or one catalog from each sub-directory of `dir`.
+pub fn load_catalog_dirs(dir: &Path) -> Result<Vec<Catalog>> {
+    if !component_files(dir, META_FILE)?.is_empty() {
+        return Ok(vec![Catalog::from_dir(dir)?]);
+    }
+    let mut sub_dirs = fs::read_dir(dir)
+        .with_context(|| format!("Could not read catalog directory {}", dir.display()))?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    sub_dirs.retain(|path| path.is_dir());
+    sub_dirs.sort();
+
+    let mut catalogs: Vec<Catalog> = Vec::new();
+    for sub_dir in sub_dirs {
+        if component_files(&sub_dir, META_FILE)?.is_empty() {
+            continue;
+        }
+        let catalog = Catalog::from_dir(&sub_dir)?;
+        if catalogs.contains(&catalog) {
+            bail!(
+                "More than one catalog for {} (again in {})",
+                catalog.low_year,
+                sub_dir.display()
+            );
+        }
+        catalogs.push(catalog);
+    }
+    if catalogs.is_empty() {
+        bail!("No catalogs found in {}", dir.display());
+    }
+    Ok(catalogs)
+}
+
+// On-disk shapes of the catalog data files. Each component is read from `<name>.toml`,
+// `<name>.json`, and/or every TOML/JSON file inside a `<name>/` directory.
+const META_FILE: &str = "catalog";
+const COURSES_FILE: &str = "courses";
+const PREREQS_FILE: &str = "prereqs";
+const GENEDS_FILE: &str = "geneds";
+const PROGRAMS_FILE: &str = "programs";
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct CatalogMeta {
+    low_year: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+struct GenEdFile {
+    geneds: Vec<GenEd>,
+}
+
+fn is_data_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("toml") | Some("json")
+    )
+}
+
+fn read_data_file<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let text = fs::read_to_string(path)
+        .with_context(|| format!("Could not read catalog file {}", path.display()))?;
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&text)
+            .with_context(|| format!("Invalid TOML in catalog file {}", path.display())),
+        Some("json") => serde_json::from_str(&text)
+            .with_context(|| format!("Invalid JSON in catalog file {}", path.display())),
+        _ => bail!("Unsupported catalog file type: {}", path.display()),
+    }
+}
+
+/// Collect every data file making up the component `name` of the catalog in `dir`.
+fn component_files(dir: &Path, name: &str) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for ext in ["toml", "json"] {
+        let path = dir.join(format!("{name}.{ext}"));
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    let sub_dir = dir.join(name);
+    if sub_dir.is_dir() {
+        let mut entries = fs::read_dir(&sub_dir)
+            .with_context(|| format!("Could not read catalog directory {}", sub_dir.display()))?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<Vec<_>>>()?;
+        entries.retain(|path| path.is_file() && is_data_file(path));
+        entries.sort();
+        files.extend(entries);
+    }
+    Ok(files)
+}
+
+fn read_component<T: DeserializeOwned>(dir: &Path, name: &str) -> Result<Vec<(PathBuf, T)>> {
+    component_files(dir, name)?
+        .into_iter()
+        .map(|path| read_data_file(&path).map(|data| (path, data)))
+        .collect()
+}
+
+fn merge_maps<V>(
+    parts: Vec<(PathBuf, HashMap<CourseCode, V>)>,
+    what: &str,
+) -> Result<HashMap<CourseCode, V>> {
+    let mut merged = HashMap::new();
+    for (path, part) in parts {
+        for (code, value) in part {
+            if merged.insert(code.clone(), value).is_some() {
+                bail!(
+                    "{what} for {code} defined more than once (again in {})",
+                    path.display()
+                );
+            }
+        }
+    }
+    Ok(merged)
+}
+
+impl Catalog {
+    /// Load a catalog from a directory of TOML/JSON data files (see [`Catalog::save_dir`] for the layout).
+    pub fn from_dir(dir: &Path) -> Result<Catalog> {
+        let low_year = match read_component::<CatalogMeta>(dir, META_FILE)?.as_slice() {
+            [(_, meta)] => meta.low_year,
+            [] => bail!(
+                "No {META_FILE}.toml or {META_FILE}.json in {}",
+                dir.display()
+            ),
+            _ => bail!("More than one {META_FILE} file in {}", dir.display()),
+        };
+
+        let courses = merge_maps(
+            read_component::<HashMap<CourseCode, CourseInfo>>(dir, COURSES_FILE)?,
+            "Course",
+        )?;
+
+        let prereqs = merge_maps(
+            read_component::<HashMap<CourseCode, CourseReq>>(dir, PREREQS_FILE)?,
+            "Prereq",
+        )?;
+
+        let geneds = read_component::<GenEdFile>(dir, GENEDS_FILE)?
+            .into_iter()
+            .flat_map(|(_, file)| file.geneds)
+            .collect();
+
+        let mut programs: Vec<Program> = Vec::new();
+        for (path, prog) in read_component::<Program>(dir, PROGRAMS_FILE)? {
+            if programs.iter().any(|p| p.name == prog.name) {
+                bail!(
+                    "Program {} defined more than once (again in {})",
+                    prog.name,
+                    path.display()
+                );
+            }
+            programs.push(prog);
+        }
+
+        Ok(Catalog {
+            programs,
+            geneds,
+            prereqs,
+            courses,
+            low_year,
+        })
+    }
+
+    /// Write this catalog as TOML data files into `dir`, in the layout read by [`Catalog::from_dir`]:
+    /// `catalog.toml`, `courses.toml`, `prereqs.toml`, `geneds.toml` and one file per program in `programs/`.
+    pub fn save_dir(&self, dir: &Path) -> Result<()> {
+        fs::create_dir_all(dir.join(PROGRAMS_FILE))?;
+
+        let write = |path: PathBuf, text: String| {
+            fs::write(&path, text)
+                .with_context(|| format!("Could not write catalog file {}", path.display()))
+        };
+
+        write(
+            dir.join(format!("{META_FILE}.toml")),
+            toml::to_string(&CatalogMeta {
+                low_year: self.low_year,
+            })?,
+        )?;
+
+        // BTreeMaps keep the files sorted by course code, so edits diff cleanly
+        let courses: BTreeMap<String, &CourseInfo> = self
+            .courses
+            .iter()
+            .map(|(code, info)| (String::from(code.clone()), info))
+            .collect();
+        write(
+            dir.join(format!("{COURSES_FILE}.toml")),
+            toml::to_string(&courses)?,
+        )?;
+
+        let prereqs: BTreeMap<String, &CourseReq> = self
+            .prereqs
+            .iter()
+            .map(|(code, req)| (String::from(code.clone()), req))
+            .collect();
+        write(
+            dir.join(format!("{PREREQS_FILE}.toml")),
+            toml::to_string(&prereqs)?,
+        )?;
+
+        write(
+            dir.join(format!("{GENEDS_FILE}.toml")),
+            toml::to_string(&GenEdFile {
+                geneds: self.geneds.clone(),
+            })?,
+        )?;
+
+        for prog in &self.programs {
+            let fname: String = prog
+                .name
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() {
+                        c.to_ascii_lowercase()
+                    } else {
+                        '_'
+                    }
+                })
+                .collect();
+            write(
+                dir.join(PROGRAMS_FILE).join(format!("{fname}.toml")),
+                toml::to_string(prog)?,
+            )?;
+        }
+        Ok(())
+    }
 }
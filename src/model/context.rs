@@ -1,25 +1,70 @@
 //! Context struct for model building and shared state.
-use crate::prereqs::CourseReq;
-use crate::schedule::{Catalog, CourseCode, Schedule};
-use cp_sat::builder::{BoolVar, CpModelBuilder, LinearExpr};
+use crate::critical_path::{Availability, CriticalPath};
+use crate::prereqs::{CourseReq, Grade};
+use crate::schedule::{
+    Catalog, CourseCode, Elective, Schedule, Season, SummerOptions, Term, Transcript,
+};
+use cp_sat::builder::{BoolVar, CpModelBuilder, IntVar, LinearExpr};
+use cp_sat::proto::CpSolverResponse;
+use std::collections::HashMap;
 
 #[derive(Clone)]
 pub struct Course {
     pub code: CourseCode,
+    /// Minimum credits; the credit value for fixed-credit courses.
     pub credits: i64,
+    pub max_credits: i64,
     pub required: bool,
     pub prereqs: CourseReq,
 }
 
+impl Course {
+    pub fn is_variable(&self) -> bool {
+        self.max_credits > self.credits
+    }
+}
+
+/// A grade-gated prereq the transcript shows was missed: `course` needs `prereq` with at least `min`,
+/// but it was passed with `grade`.
+#[derive(Clone)]
+pub struct FailedGate {
+    pub course: CourseCode,
+    pub prereq: CourseCode,
+    pub min: Grade,
+    pub grade: Grade,
+}
+
 pub struct ModelBuilderContext<'a> {
     pub model: CpModelBuilder,
     pub vars: Vec<Vec<BoolVar>>,
+    /// Per-semester credits taken for variable-credit courses (0 when not scheduled that semester);
+    /// `None` for fixed-credit courses.
+    pub credit_vars: Vec<Option<Vec<IntVar>>>,
     pub courses: Vec<Course>,
     pub num_semesters: usize,
+    /// Term of each semester; `None` for semester 0 (incoming)
+    pub terms: Vec<Option<Term>>,
     pub max_credits_per_semester: i64,
+    pub summer: SummerOptions,
+    /// Whether each summer term is used at all, in order
+    pub summer_used: Vec<BoolVar>,
     pub min_credits: Option<i64>,
     pub catalog: Option<&'a Catalog>,
+    /// Elective groups of the programs in the schedule
+    pub program_electives: Vec<&'a Elective>,
+    /// Incoming courses fixed in semester 0; failed gates of planned courses are left out, so they can
+    /// be retaken in a planned semester
     pub incoming_codes: Vec<CourseCode>,
+    /// Grade gates of planned courses the transcript shows were missed
+    pub failed_gates: Vec<FailedGate>,
+    pub transcript: Transcript,
+    /// Courses with instructor consent, whose `Instructor` prereqs are satisfied
+    pub consents: Vec<CourseCode>,
+    /// Stems associated with the programs in the schedule, which satisfy `Program` prereqs
+    pub program_stems: Vec<String>,
+    pub test_scores: HashMap<String, u32>,
+    /// Earliest semester each course could be taken in after semester 0, from the critical path
+    pub earliest: Vec<Availability>,
 }
 
 impl<'a> ModelBuilderContext<'a> {
@@ -53,7 +98,11 @@ impl<'a> ModelBuilderContext<'a> {
                                 collect_prereq_codes(r, all_codes, queue);
                             }
                         }
-                        PreCourse(code) | CoCourse(code) => {
+                        PreCourse(code)
+                        | CoCourse(code)
+                        | PreCourseGrade(code, _)
+                        | CoCourseGrade(code, _)
+                        | SameTermCourse(code) => {
                             if all_codes.insert(code.clone()) {
                                 queue.push_back(code.clone());
                             }
@@ -91,10 +140,12 @@ impl<'a> ModelBuilderContext<'a> {
                 }
             }
         }
-        // Collect program electives for the selected programs
-        let mut program_electives: Vec<&crate::schedule::Elective> = Vec::new();
+        // Collect program electives and stems for the selected programs
+        let mut program_electives: Vec<&Elective> = Vec::new();
+        let mut program_stems: Vec<String> = Vec::new();
         for prog_name in &sched.programs {
             if let Some(prog) = sched.catalog.programs.iter().find(|p| &p.name == prog_name) {
+                program_stems.extend(prog.assoc_stems.iter().cloned());
                 for elective in &prog.electives {
                     program_electives.push(elective);
                     // Also ensure elective courses are included in all_codes so they can be modelled
@@ -121,18 +172,27 @@ impl<'a> ModelBuilderContext<'a> {
         // Build Course structs for all codes, and print diagnostics
         let mut courses = Vec::new();
         for code in &all_codes {
-            let (credits, prereqs) = match sched.catalog.courses.get(code) {
-                Some((_name, credits_opt, _offering)) => {
-                    let credits = credits_opt.unwrap_or(0) as i64;
+            let (credits, max_credits, prereqs) = match sched.catalog.courses.get(code) {
+                Some(info) => {
+                    // A credit value already chosen in the schedule is kept as is, and incoming
+                    // courses without one count their minimum, as in Schedule::course_credits
+                    let (min, max) = match sched.credits.get(code) {
+                        Some(&chosen) => (chosen, chosen),
+                        None if sched.incoming.contains(code) => {
+                            (info.min_credits(), info.min_credits())
+                        }
+                        None => (info.min_credits(), info.max_credits()),
+                    };
+                    // Normalized trees make for fewer reified literals in the model
                     let prereqs = sched
                         .catalog
                         .prereqs
                         .get(code)
-                        .cloned()
+                        .map(CourseReq::normalize)
                         .unwrap_or(CourseReq::NotRequired);
-                    (credits, prereqs)
+                    (min as i64, max as i64, prereqs)
                 }
-                None => (0, CourseReq::NotRequired),
+                None => (0, 0, CourseReq::NotRequired),
             };
             let required = if sched.incoming.contains(code) {
                 true
@@ -142,20 +202,73 @@ impl<'a> ModelBuilderContext<'a> {
             courses.push(Course {
                 code: code.clone(),
                 credits,
+                max_credits,
                 required,
                 prereqs,
             });
         }
 
+        // A planned course whose grade gate was missed needs that course retaken, so it is no longer
+        // fixed in semester 0
+        let planned = |code: &CourseCode| sched.courses.iter().flatten().any(|c| c == code);
+        let mut failed_gates = Vec::new();
+        for course in courses.iter().filter(|c| planned(&c.code)) {
+            for (prereq, min) in course.prereqs.grade_gates() {
+                if let Some(grade) = sched.transcript.grade(prereq).filter(|grade| *grade < min) {
+                    failed_gates.push(FailedGate {
+                        course: course.code.clone(),
+                        prereq: prereq.clone(),
+                        min: min.clone(),
+                        grade: grade.clone(),
+                    });
+                }
+            }
+        }
+        for course in &mut courses {
+            if failed_gates.iter().any(|g| g.prereq == course.code) {
+                course.required = planned(&course.code);
+            }
+        }
+        let incoming_codes = sched
+            .incoming
+            .iter()
+            .filter(|code| !failed_gates.iter().any(|g| &g.prereq == *code))
+            .cloned()
+            .collect();
+
+        let terms: Vec<Option<Term>> = (0..sched.courses.len())
+            .map(|s| s.checked_sub(1).map(|sem_idx| sched.term(sem_idx)))
+            .collect();
+        let critical_path = CriticalPath::new(
+            &sched.catalog,
+            &sched.incoming,
+            terms.iter().flatten().copied().collect(),
+        );
+        let earliest = courses
+            .iter()
+            .map(|c| critical_path.earliest(&c.code))
+            .collect();
+
         ModelBuilderContext {
             model: CpModelBuilder::default(),
             vars: Vec::new(),
+            credit_vars: Vec::new(),
             courses,
             num_semesters: sched.courses.len(), // already includes semester 0 after transformation
+            terms,
             max_credits_per_semester,
+            summer: sched.summer.clone(),
+            summer_used: Vec::new(),
             min_credits: None,
             catalog: Some(&sched.catalog),
-            incoming_codes: sched.incoming.clone(),
+            program_electives,
+            incoming_codes,
+            failed_gates,
+            transcript: sched.transcript.clone(),
+            consents: sched.consents.clone(),
+            program_stems,
+            test_scores: sched.test_scores.clone(),
+            earliest,
         }
     }
 
@@ -164,20 +277,99 @@ impl<'a> ModelBuilderContext<'a> {
         self.min_credits = Some(min_credits);
     }
 
+    /// Credits course `idx` contributes to semester `s` (0 if it is not scheduled there)
+    pub fn credits_expr(&self, idx: usize, s: usize) -> LinearExpr {
+        match &self.credit_vars[idx] {
+            Some(credit_vars) => LinearExpr::from(credit_vars[s]),
+            None => LinearExpr::from((self.courses[idx].credits, self.vars[idx][s])),
+        }
+    }
+
+    /// 1 if course `idx` is scheduled in any semester (including semester 0), else 0
+    pub fn course_in_schedule(&self, idx: usize) -> LinearExpr {
+        let mut expr = LinearExpr::from(0);
+        for s in 0..self.num_semesters {
+            expr += LinearExpr::from(self.vars[idx][s]);
+        }
+        expr
+    }
+
+    /// Credits course `idx` counts for across all semesters (0 if it is not scheduled)
+    pub fn credits_in_schedule(&self, idx: usize) -> LinearExpr {
+        let mut expr = LinearExpr::from(0);
+        for s in 0..self.num_semesters {
+            expr += self.credits_expr(idx, s);
+        }
+        expr
+    }
+
+    /// Whether `code` was passed below a grade gate of a planned course and may be retaken
+    pub fn is_retake(&self, code: &CourseCode) -> bool {
+        self.failed_gates.iter().any(|g| &g.prereq == code)
+    }
+
+    /// Credits earned in the semesters before `s`, including semester 0 (incoming). A retake earned
+    /// its credit in semester 0 and earns none again.
+    pub fn credits_before_expr(&self, s: usize) -> LinearExpr {
+        let mut expr = LinearExpr::from(0);
+        for idx in 0..self.courses.len() {
+            if self.is_retake(&self.courses[idx].code) {
+                if s > 0 {
+                    expr += LinearExpr::from(self.courses[idx].credits);
+                }
+                continue;
+            }
+            for t in 0..s {
+                expr += self.credits_expr(idx, t);
+            }
+        }
+        expr
+    }
+
+    /// Upper bound on the credits of any set of semesters, for big-M constraints
+    pub fn max_total_credits(&self) -> i64 {
+        self.courses.iter().map(|c| c.max_credits).sum()
+    }
+
+    /// Indices in `courses` of the modelled courses among `codes`
+    pub fn known_indices(&self, codes: &[CourseCode]) -> Vec<usize> {
+        codes
+            .iter()
+            .filter_map(|code| self.courses.iter().position(|c| &c.code == code))
+            .collect()
+    }
+
+    /// Credits course `idx` was given in semester `s` of a solved model
+    pub fn solution_credits(&self, response: &CpSolverResponse, idx: usize, s: usize) -> i64 {
+        match &self.credit_vars[idx] {
+            Some(credit_vars) => credit_vars[s].solution_value(response),
+            None if self.vars[idx][s].solution_value(response) => self.courses[idx].credits,
+            None => 0,
+        }
+    }
+
+    pub fn is_summer(&self, s: usize) -> bool {
+        self.terms[s].is_some_and(|term| term.season == Season::Summer)
+    }
+
+    /// Objective penalty for the summer terms in use
+    pub fn summer_cost_expr(&self) -> LinearExpr {
+        self.summer_used
+            .iter()
+            .map(|&used| (self.summer.cost as i64, used))
+            .collect()
+    }
+
     /// Compute the total credits LinearExpr for the current context
-    pub fn total_credits_expr(
-        &self,
-        vars: &[Vec<BoolVar>],
-        flat_courses: &[(Course, i64)],
-    ) -> LinearExpr {
-        let mut obj_terms = Vec::new();
-        for (i, (_course, credits)) in flat_courses.iter().enumerate() {
+    pub fn total_credits_expr(&self) -> LinearExpr {
+        let mut expr = LinearExpr::from(0);
+        for i in 0..self.courses.len() {
             // Skip semester 0 (incoming) when computing total scheduled credits
             for s in 1..self.num_semesters {
-                obj_terms.push((*credits, vars[i][s]));
+                expr += self.credits_expr(i, s);
             }
         }
-        obj_terms.into_iter().collect()
+        expr
     }
 }
 
@@ -188,6 +380,7 @@ pub fn build_model_pipeline(
     super::courses::add_courses(ctx);
     super::prereqs::add_prereq_constraints(ctx);
     super::geneds::add_gened_constraints(ctx);
+    super::electives::add_elective_constraints(ctx);
     super::semester::add_semester_constraints(ctx);
     // Build flat_courses as (Course, credits)
     let flat_courses = ctx.courses.iter().map(|c| (c.clone(), c.credits)).collect();
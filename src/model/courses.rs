@@ -1,5 +1,6 @@
 //! Functions for adding course variables and required/optional constraints.
 use super::context::ModelBuilderContext;
+use crate::critical_path::Availability;
 
 pub fn add_courses<'a>(ctx: &mut ModelBuilderContext<'a>) {
     let mut vars = Vec::new();
@@ -12,6 +13,25 @@ pub fn add_courses<'a>(ctx: &mut ModelBuilderContext<'a>) {
         vars.push(sem_vars);
     }
     ctx.vars = vars;
+    // Variable-credit courses get an integer credit value per semester, within range when scheduled
+    let mut credit_vars = Vec::new();
+    for (i, c) in ctx.courses.iter().enumerate() {
+        if !c.is_variable() {
+            credit_vars.push(None);
+            continue;
+        }
+        let mut sem_credit_vars = Vec::new();
+        for s in 0..ctx.num_semesters {
+            let v = ctx
+                .model
+                .new_int_var_with_name([(0, c.max_credits)], format!("cr_{i}_{s}"));
+            ctx.model.add_ge(v, (c.credits, ctx.vars[i][s]));
+            ctx.model.add_le(v, (c.max_credits, ctx.vars[i][s]));
+            sem_credit_vars.push(v);
+        }
+        credit_vars.push(Some(sem_credit_vars));
+    }
+    ctx.credit_vars = credit_vars;
     // Required courses exactly once
     for (i, c) in ctx.courses.iter().enumerate() {
         if c.required {
@@ -24,10 +44,23 @@ pub fn add_courses<'a>(ctx: &mut ModelBuilderContext<'a>) {
             ctx.model.add_at_most_one(ctx.vars[i].iter().copied());
         }
     }
+    // Cross-listed courses are one course, taken at most once between them unless the plan already
+    // lists both
+    if let Some(catalog) = ctx.catalog {
+        for i in 0..ctx.courses.len() {
+            for j in i + 1..ctx.courses.len() {
+                let (a, b) = (&ctx.courses[i], &ctx.courses[j]);
+                if (a.required && b.required) || !catalog.is_same_course(&a.code, &b.code) {
+                    continue;
+                }
+                ctx.model
+                    .add_at_most_one(ctx.vars[i].iter().chain(&ctx.vars[j]).copied());
+            }
+        }
+    }
     // // Enforce maximum total credits if specified
     if let Some(min_credits) = ctx.min_credits {
-        let flat_courses: Vec<_> = ctx.courses.iter().map(|c| (c.clone(), c.credits)).collect();
-        let total_credits_expr = ctx.total_credits_expr(&ctx.vars, &flat_courses);
+        let total_credits_expr = ctx.total_credits_expr();
         ctx.model.add_le(
             total_credits_expr,
             cp_sat::builder::LinearExpr::from(min_credits),
@@ -39,16 +72,12 @@ pub fn add_courses<'a>(ctx: &mut ModelBuilderContext<'a>) {
         let offering = ctx
             .catalog
             .and_then(|cat| cat.courses.get(&c.code))
-            .map(|(_, _, off)| off);
+            .map(|info| &info.offering);
         for s in 0..ctx.num_semesters {
-            let allowed = match offering {
-                Some(crate::schedule::CourseTermOffering::Fall) => (s == 0) || (s % 2 == 1), // odd semesters
-                Some(crate::schedule::CourseTermOffering::Spring) => (s == 0) || (s % 2 == 0), // even semesters
-                Some(crate::schedule::CourseTermOffering::Both) => (s == 0) || true,
-                Some(crate::schedule::CourseTermOffering::Discretion) => (s == 0) || true, // allowed, but may change in future
-                Some(crate::schedule::CourseTermOffering::Infrequently) => (s == 0) || true, // allowed, but may change in future
-                Some(crate::schedule::CourseTermOffering::Summer) => (s == 0) || false, // never schedule
-                None => true, // default: allow
+            // Incoming courses are already taken, and courses missing from the catalog are allowed anywhere
+            let allowed = match (offering, &ctx.terms[s]) {
+                (Some(offering), Some(term)) => offering.is_offered(term),
+                _ => true,
             };
             if !allowed {
                 // Forbid scheduling this course in this semester
@@ -57,6 +86,20 @@ pub fn add_courses<'a>(ctx: &mut ModelBuilderContext<'a>) {
             }
         }
     }
+    // Nothing is taken before its prerequisites could be; this only prunes, the prereq constraints
+    // still decide
+    for (i, earliest) in ctx.earliest.iter().enumerate() {
+        let first = match earliest {
+            Availability::Incoming => continue,
+            // Semester `sem_idx` of the critical path is semester `sem_idx + 1` of the model
+            Availability::Semester(sem_idx) => sem_idx + 1,
+            Availability::Unreachable => ctx.num_semesters,
+        };
+        for s in 1..first {
+            ctx.model
+                .add_eq(ctx.vars[i][s], cp_sat::builder::LinearExpr::from(0));
+        }
+    }
     // --- Incoming courses logic ---
     // Get incoming codes from context (they are always required and only scheduled in semester 0)
     let incoming_semester = 0;
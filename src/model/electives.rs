@@ -0,0 +1,10 @@
+//! Functions for adding program elective constraints.
+use super::context::ModelBuilderContext;
+use super::geneds::add_elective_req_constraints;
+
+/// Add a constraint for every elective group of the scheduled programs.
+pub fn add_elective_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
+    for elective in ctx.program_electives.clone() {
+        add_elective_req_constraints(ctx, &elective.req);
+    }
+}
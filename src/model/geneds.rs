@@ -3,17 +3,73 @@ use super::context::ModelBuilderContext;
 use crate::geneds::{ElectiveReq, GenEd};
 use cp_sat::builder::LinearExpr;
 
+/// Require the scheduled courses to meet `req`. The courses may count towards other requirements too.
+/// Courses that are not modelled can never be scheduled, so they count as missing rather than dropping
+/// the constraint.
+pub(super) fn add_elective_req_constraints(ctx: &mut ModelBuilderContext, req: &ElectiveReq) {
+    match req {
+        ElectiveReq::Set(codes) => {
+            // Each course must be scheduled somewhere
+            let mut present = LinearExpr::from(0);
+            for idx in ctx.known_indices(codes) {
+                present += ctx.course_in_schedule(idx);
+            }
+            ctx.model
+                .add_ge(present, LinearExpr::from(codes.len() as i64));
+        }
+        ElectiveReq::SetOpts(opts) => {
+            // At least one option set must be fully present
+            let mut options = LinearExpr::from(0);
+            for opt in opts {
+                let mut all_present = LinearExpr::from(0);
+                for idx in ctx.known_indices(opt) {
+                    all_present += ctx.course_in_schedule(idx);
+                }
+                // The option counts only if all its courses are present
+                let opt_var = ctx.model.new_bool_var();
+                ctx.model
+                    .add_ge(all_present, LinearExpr::from((opt.len() as i64, opt_var)));
+                options += opt_var;
+            }
+            ctx.model.add_ge(options, LinearExpr::from(1));
+        }
+        ElectiveReq::Courses { num, courses } => {
+            let mut sum = LinearExpr::from(0);
+            for idx in ctx.known_indices(courses) {
+                sum += ctx.course_in_schedule(idx);
+            }
+            ctx.model.add_ge(sum, LinearExpr::from(*num as i64));
+        }
+        ElectiveReq::Credits { num, courses } => {
+            let mut sum = LinearExpr::from(0);
+            for idx in ctx.known_indices(courses) {
+                sum += ctx.credits_in_schedule(idx);
+            }
+            ctx.model.add_ge(sum, LinearExpr::from(*num as i64));
+        }
+    }
+}
+
 /// Add GenEd constraints to the model.
 pub fn add_gened_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
-    let model = &mut ctx.model;
-    let courses = &ctx.courses;
-    let vars = &ctx.vars;
-    let num_semesters = ctx.num_semesters;
     let geneds = match ctx.catalog {
         Some(catalog) => &catalog.geneds,
         None => return,
     };
 
+    // --- Core GenEds: no overlap restrictions ---
+    for gened in geneds.iter() {
+        if let GenEd::Core { req, .. } = gened {
+            add_elective_req_constraints(ctx, req);
+        }
+    }
+
+    let model = &mut ctx.model;
+    let courses = &ctx.courses;
+    let vars = &ctx.vars;
+    let credit_vars = &ctx.credit_vars;
+    let num_semesters = ctx.num_semesters;
+
     // Helper: for a course code, find its index in flat_courses
     let code_to_idx: std::collections::HashMap<_, _> = courses
         .iter()
@@ -31,99 +87,24 @@ pub fn add_gened_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
         expr
     };
 
+    // Helper: credits a course counts for across all semesters (0 if it is not scheduled); variable-credit
+    // courses count the credits chosen by the solver
+    let credits_in_schedule = |idx: usize| {
+        let mut expr = LinearExpr::from(0);
+        for s in 0..num_semesters {
+            match &credit_vars[idx] {
+                Some(cvs) => expr += LinearExpr::from(cvs[s]),
+                None => expr += (courses[idx].credits, vars[idx][s]),
+            }
+        }
+        expr
+    };
+
     // Helper: for a set of course codes, return a vector of their indices (if all present)
     let codes_to_indices = |codes: &Vec<crate::schedule::CourseCode>| -> Option<Vec<usize>> {
         codes.iter().map(|c| code_to_idx.get(c).copied()).collect()
     };
 
-    // --- Core GenEds: no overlap restrictions ---
-    for gened in geneds.iter() {
-        if let GenEd::Core { req, .. } = gened {
-            match req {
-                ElectiveReq::Set(codes) => {
-                    if let Some(indices) = codes_to_indices(codes) {
-                        for idx in indices {
-                            // Each course must be scheduled somewhere
-                            model.add_ge(course_in_schedule(idx), LinearExpr::from(1));
-                        }
-                    }
-                }
-                ElectiveReq::SetOpts(opts) => {
-                    // At least one option set must be fully present
-                    let mut option_exprs = Vec::new();
-                    for opt in opts {
-                        if let Some(indices) = codes_to_indices(opt) {
-                            // All courses in this option must be present
-                            let mut all_present = LinearExpr::from(0);
-                            for idx in indices {
-                                all_present += course_in_schedule(idx);
-                            }
-                            // If all are present, sum == len
-                            // Use a bool var to represent this option
-                            let opt_var = model.new_bool_var();
-                            // model.add_le(all_present.clone(), LinearExpr::from(opt.len() as i64) + (LinearExpr::from(1) - opt_var.clone()) * (opt.len() as i64));
-                            // model.add_ge(all_present, LinearExpr::from(opt.len() as i64) - (LinearExpr::from(1) - opt_var.clone()) * (opt.len() as i64));
-                            // Instead, expand (LinearExpr * n) as repeated addition
-                            let le_expr = LinearExpr::from(1) - opt_var;
-                            let mut le_scaled = LinearExpr::from(0);
-                            for _ in 0..opt.len() {
-                                le_scaled += le_expr.clone();
-                            }
-                            model.add_le(
-                                all_present.clone(),
-                                LinearExpr::from(opt.len() as i64) + le_scaled,
-                            );
-                            let mut ge_scaled = LinearExpr::from(0);
-                            for _ in 0..opt.len() {
-                                ge_scaled += le_expr.clone();
-                            }
-                            model.add_ge(
-                                all_present,
-                                LinearExpr::from(opt.len() as i64) - ge_scaled,
-                            );
-                            option_exprs.push(opt_var);
-                        }
-                    }
-                    if !option_exprs.is_empty() {
-                        let mut sum = LinearExpr::from(0);
-                        for v in option_exprs {
-                            sum += LinearExpr::from(v);
-                        }
-                        model.add_ge(sum, LinearExpr::from(1));
-                    }
-                }
-                ElectiveReq::Courses { num, courses } => {
-                    if let Some(indices) = codes_to_indices(courses) {
-                        let mut sum = LinearExpr::from(0);
-                        for idx in indices {
-                            sum += course_in_schedule(idx);
-                        }
-                        model.add_ge(sum, LinearExpr::from(*num as i64));
-                    }
-                }
-                ElectiveReq::Credits { num, courses } => {
-                    if let Some(indices) = codes_to_indices(courses) {
-                        let mut sum = LinearExpr::from(0);
-                        for idx in indices.iter() {
-                            let credits = ctx.courses[*idx].credits;
-                            // sum = sum + course_in_schedule(*idx) * credits;
-                            if credits > 0 {
-                                for _ in 0..credits {
-                                    sum += course_in_schedule(*idx);
-                                }
-                            } else if credits < 0 {
-                                for _ in 0..(-credits) {
-                                    sum -= course_in_schedule(*idx);
-                                }
-                            }
-                        }
-                        model.add_ge(sum, LinearExpr::from(*num as i64));
-                    }
-                }
-            }
-        }
-    }
-
     // --- Foundation GenEds: enforce required number/credits, no course may satisfy more than one Foundation ---
     // For each Foundation, build a set of eligible courses and add a hard constraint for the requirement
     let mut foundation_sets = Vec::new();
@@ -196,16 +177,7 @@ pub fn add_gened_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
                     if let Some(indices) = codes_to_indices(courses) {
                         let mut sum = LinearExpr::from(0);
                         for idx in &indices {
-                            let credits = ctx.courses[*idx].credits;
-                            if credits > 0 {
-                                for _ in 0..credits {
-                                    sum += course_in_schedule(*idx);
-                                }
-                            } else if credits < 0 {
-                                for _ in 0..(-credits) {
-                                    sum -= course_in_schedule(*idx);
-                                }
-                            }
+                            sum += credits_in_schedule(*idx);
                         }
                         model.add_ge(sum, LinearExpr::from(*num as i64));
                         foundation_sets.push(indices);
@@ -229,7 +201,10 @@ pub fn add_gened_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
         let (required, is_credits, course_credits) = match gened {
             GenEd::Foundation { req, .. } => match req {
                 ElectiveReq::Credits { num, .. } => {
-                    let credits: Vec<_> = set.iter().map(|&idx| ctx.courses[idx].credits).collect();
+                    let credits: Vec<_> = set
+                        .iter()
+                        .map(|&idx| ctx.courses[idx].max_credits)
+                        .collect();
                     (*num as i64, true, credits)
                 }
                 ElectiveReq::Set(_) => (set.len() as i64, false, vec![1; set.len()]), // FIXME?
@@ -256,24 +231,18 @@ pub fn add_gened_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
         // All required courses must count toward the Foundation
         let mut required_sum = LinearExpr::from(0);
         for &idx in &required_idxs {
-            let c = if *is_credits {
-                ctx.courses[idx].credits
+            if *is_credits {
+                required_sum += credits_in_schedule(idx);
             } else {
-                1
-            };
-            for _ in 0..c {
                 required_sum += course_in_schedule(idx);
             }
         }
         // Optional courses can be used to reach the minimum, but not to over-satisfy
         let mut optional_sum = LinearExpr::from(0);
         for &idx in &optional_idxs {
-            let c = if *is_credits {
-                ctx.courses[idx].credits
+            if *is_credits {
+                optional_sum += credits_in_schedule(idx);
             } else {
-                1
-            };
-            for _ in 0..c {
                 optional_sum += course_in_schedule(idx);
             }
         }
@@ -285,7 +254,7 @@ pub fn add_gened_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
         // The total (required + optional) cannot exceed the maximum of required_sum and required
         // Set the domain to the true maximum possible sum (credits or count)
         let max_possible = if *is_credits {
-            set.iter().map(|&idx| ctx.courses[idx].credits).sum()
+            set.iter().map(|&idx| ctx.courses[idx].max_credits).sum()
         } else {
             set.len() as i64
         };
@@ -415,15 +384,10 @@ pub fn add_gened_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
                         let mut required_sum = LinearExpr::from(0);
                         let mut optional_sum = LinearExpr::from(0);
                         for &idx in &indices {
-                            let credits = ctx.courses[idx].credits;
                             if ctx.courses[idx].required {
-                                for _ in 0..credits {
-                                    required_sum += course_in_schedule(idx);
-                                }
+                                required_sum += credits_in_schedule(idx);
                             } else {
-                                for _ in 0..credits {
-                                    optional_sum += course_in_schedule(idx);
-                                }
+                                optional_sum += credits_in_schedule(idx);
                             }
                         }
                         let required = *num as i64;
@@ -431,8 +395,10 @@ pub fn add_gened_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
                             required_sum.clone() + optional_sum.clone(),
                             LinearExpr::from(required),
                         );
-                        let max_possible =
-                            indices.iter().map(|&idx| ctx.courses[idx].credits).sum();
+                        let max_possible = indices
+                            .iter()
+                            .map(|&idx| ctx.courses[idx].max_credits)
+                            .sum();
                         let max_expr = model.new_int_var([(0, max_possible)]);
                         model.add_ge(max_expr, required_sum.clone());
                         model.add_ge(max_expr, LinearExpr::from(required));
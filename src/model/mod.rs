@@ -2,6 +2,7 @@
 
 mod context;
 mod courses;
+mod electives;
 mod geneds;
 mod prereqs;
 mod semester;
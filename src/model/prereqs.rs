@@ -1,8 +1,12 @@
 //! Functions for adding prerequisite constraints.
+//!
+//! Every node of a prereq tree gets its own boolean per semester, implying that the subtree holds in
+//! that semester; a course can only be placed in a semester where the root's boolean is set. Prereq
+//! trees have no negation, so this one-sided encoding loses no solutions.
 use super::context::ModelBuilderContext;
-use crate::prereqs::CourseReq;
+use crate::prereqs::{CourseReq, Standing};
 use crate::schedule::CourseCode;
-use cp_sat::builder::LinearExpr;
+use cp_sat::builder::{BoolVar, LinearExpr};
 use std::collections::HashMap;
 
 pub fn add_prereq_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
@@ -19,115 +23,194 @@ pub fn add_prereq_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
     }
 }
 
-fn add_prereq_for_course<'a>(
-    ctx: &mut ModelBuilderContext<'a>,
+/// Resolve the parts of a prereq of course `course_idx` known before solving. A grade-gated prereq is
+/// modelled like its plain counterpart; if the transcript shows the course was passed below the
+/// minimum grade, only a retake in a planned semester meets it, while every other prereq on a retaken
+/// course already holds from semester 0. Instructor consent is either recorded or not, program
+/// restrictions are fixed by the selected programs, and test scores are known. A credits-earned
+/// prereq is a minimum standing. `None` means the prereq cannot be satisfied.
+fn resolve(ctx: &ModelBuilderContext, course_idx: usize, req: &CourseReq) -> Option<CourseReq> {
+    use crate::prereqs::CourseReq::*;
+    match req {
+        Instructor if ctx.consents.contains(&ctx.courses[course_idx].code) => Some(NotRequired),
+        Instructor => None,
+        Program(stem) if ctx.program_stems.contains(stem) => Some(NotRequired),
+        Program(_) => None,
+        TestScore(test, min) if ctx.test_scores.get(test).is_some_and(|s| s >= min) => {
+            Some(NotRequired)
+        }
+        TestScore(..) => None,
+        CreditsEarned(credits) => Some(Standing(crate::prereqs::Standing::at_least(*credits))),
+        PreCourseGrade(code, min) | CoCourseGrade(code, min)
+            if ctx.transcript.grade(code).is_some_and(|grade| grade < min) =>
+        {
+            match req {
+                _ if !ctx.is_retake(code) => None,
+                PreCourseGrade(..) => Some(PreCourse(code.clone())),
+                _ => Some(CoCourse(code.clone())),
+            }
+        }
+        PreCourse(code)
+        | CoCourse(code)
+        | SameTermCourse(code)
+        | PreCourseGrade(code, _)
+        | CoCourseGrade(code, _)
+            if ctx.is_retake(code) =>
+        {
+            Some(NotRequired)
+        }
+        PreCourseGrade(code, _) => Some(PreCourse(code.clone())),
+        CoCourseGrade(code, _) => Some(CoCourse(code.clone())),
+        _ => Some(req.clone()),
+    }
+}
+
+/// If `enforce` is set, the credits earned before semester `s` must lie within `standing`.
+fn add_standing_constraint(
+    ctx: &mut ModelBuilderContext,
+    standing: &Standing,
+    s: usize,
+    enforce: BoolVar,
+) {
+    let big_m = ctx.max_total_credits();
+    if let Some(min) = standing.min_credits {
+        // earned >= min * enforce
+        let expr = ctx.credits_before_expr(s) + (-(min as i64), enforce);
+        ctx.model.add_ge(expr, 0);
+    }
+    if let Some(max) = standing.max_credits {
+        // earned <= max + big_m * (1 - enforce)
+        let expr = ctx.credits_before_expr(s) + (big_m, enforce);
+        ctx.model.add_le(expr, max as i64 + big_m);
+    }
+}
+
+/// Whether a prereq subtree holds in a semester: fixed while building the model, or up to the solver.
+#[derive(Clone, Copy)]
+enum ReqLit {
+    True,
+    False,
+    /// Set only if the subtree holds
+    Var(BoolVar),
+}
+
+fn reify_prereq(
+    ctx: &mut ModelBuilderContext,
     idx_map: &HashMap<CourseCode, usize>,
     course_idx: usize,
     req: &CourseReq,
-) {
+    s: usize,
+) -> ReqLit {
     use crate::prereqs::CourseReq::*;
-    let num_semesters = ctx.num_semesters;
-    // If the target course is an incoming course, skip prereq constraints entirely.
-    // Incoming courses are allowed in semester 0 and should not be blocked by prereqs.
-    if ctx.incoming_codes.contains(&ctx.courses[course_idx].code) {
-        return;
-    }
-    match req {
-        NotRequired => {}
+    let Some(req) = resolve(ctx, course_idx, req) else {
+        return ReqLit::False;
+    };
+    match &req {
+        NotRequired => ReqLit::True,
+        PreCourse(code) | CoCourse(code) => {
+            let Some(&idx) = idx_map.get(code) else {
+                return ReqLit::False;
+            };
+            // A prior course must be taken in an earlier semester (semester 0 being incoming), a
+            // concurrent one may also be taken in `s`
+            let upto = match req {
+                PreCourse(_) => s,
+                _ => s + 1,
+            };
+            if upto == 0 {
+                return ReqLit::False;
+            }
+            let taken: LinearExpr = ctx.vars[idx][..upto].iter().copied().collect();
+            let holds = ctx.model.new_bool_var();
+            ctx.model.add_le(holds, taken);
+            ReqLit::Var(holds)
+        }
+        SameTermCourse(code) => {
+            let Some(&idx) = idx_map.get(code) else {
+                return ReqLit::False;
+            };
+            // Taken in `s` itself, or already incoming in semester 0
+            let mut taken = LinearExpr::from(ctx.vars[idx][0]);
+            if s > 0 {
+                taken += ctx.vars[idx][s];
+            }
+            let holds = ctx.model.new_bool_var();
+            ctx.model.add_le(holds, taken);
+            ReqLit::Var(holds)
+        }
+        Standing(standing) => {
+            let holds = ctx.model.new_bool_var();
+            add_standing_constraint(ctx, standing, s, holds);
+            ReqLit::Var(holds)
+        }
         And(reqs) => {
+            let mut parts = Vec::new();
             for r in reqs {
-                add_prereq_for_course(ctx, idx_map, course_idx, r);
+                match reify_prereq(ctx, idx_map, course_idx, r, s) {
+                    ReqLit::True => {}
+                    ReqLit::False => return ReqLit::False,
+                    ReqLit::Var(part) => parts.push(part),
+                }
             }
-        }
-        Or(reqs) => {
-            for s in 0..num_semesters {
-                let cur = ctx.vars[course_idx][s];
-                let mut or_exprs = Vec::new();
-                for r in reqs {
-                    let or_var = ctx.model.new_bool_var();
-                    match r {
-                        PreCourse(code) => {
-                            if let Some(&pre_idx) = idx_map.get(code) {
-                                // Allow prereqs to be satisfied in semester 0 (incoming)
-                                let earlier_vars: Vec<_> = ctx.vars[pre_idx][..s].to_vec();
-                                if !earlier_vars.is_empty() {
-                                    let sum_earlier: LinearExpr =
-                                        earlier_vars.into_iter().collect();
-                                    ctx.model.add_linear_constraint(
-                                        sum_earlier - or_var,
-                                        [(0, i64::MAX)],
-                                    );
-                                    or_exprs.push(or_var);
-                                }
-                            }
-                        }
-                        CoCourse(code) => {
-                            if let Some(&co_idx) = idx_map.get(code) {
-                                let upto_vars: Vec<_> = ctx.vars[co_idx][..=s].to_vec();
-                                if !upto_vars.is_empty() {
-                                    let sum_upto: LinearExpr = upto_vars.into_iter().collect();
-                                    ctx.model
-                                        .add_linear_constraint(sum_upto - or_var, [(0, i64::MAX)]);
-                                    or_exprs.push(or_var);
-                                }
-                            }
-                        }
-                        And(_) | Or(_) => {
-                            add_prereq_for_course(ctx, idx_map, course_idx, r);
-                        }
-                        _ => eprintln!("Only PreCourse, CoCourse, And, Or supported, not {r:?}"),
+            match parts.as_slice() {
+                [] => ReqLit::True,
+                [part] => ReqLit::Var(*part),
+                _ => {
+                    let holds = ctx.model.new_bool_var();
+                    for part in parts {
+                        ctx.model.add_le(holds, part);
                     }
-                    or_exprs.push(or_var);
-                }
-                if !or_exprs.is_empty() {
-                    let sum_or: LinearExpr = or_exprs.iter().copied().collect();
-                    ctx.model
-                        .add_linear_constraint(sum_or - cur, [(0, i64::MAX)]);
+                    ReqLit::Var(holds)
                 }
             }
         }
-        PreCourse(code) => {
-            if let Some(&pre_idx) = idx_map.get(code) {
-                for s in 0..num_semesters {
-                    let cur = ctx.vars[course_idx][s];
-                    if s == 0 {
-                        ctx.model.add_eq(cur, 0);
-                    } else {
-                        let earlier_vars: Vec<_> = ctx.vars[pre_idx][..s].to_vec();
-                        if !earlier_vars.is_empty() {
-                            let sum_earlier: LinearExpr = earlier_vars.into_iter().collect();
-                            ctx.model
-                                .add_linear_constraint(sum_earlier - cur, [(0, i64::MAX)]);
-                        } else {
-                            ctx.model.add_eq(cur, 0);
-                        }
-                    }
+        Or(reqs) => {
+            let mut options = Vec::new();
+            for r in reqs {
+                match reify_prereq(ctx, idx_map, course_idx, r, s) {
+                    ReqLit::True => return ReqLit::True,
+                    ReqLit::False => {}
+                    ReqLit::Var(option) => options.push(option),
                 }
-            } else {
-                for s in 0..num_semesters {
-                    ctx.model.add_eq(ctx.vars[course_idx][s], 0);
+            }
+            match options.as_slice() {
+                [] => ReqLit::False,
+                [option] => ReqLit::Var(*option),
+                _ => {
+                    let holds = ctx.model.new_bool_var();
+                    let any_option: LinearExpr = options.into_iter().collect();
+                    ctx.model.add_le(holds, any_option);
+                    ReqLit::Var(holds)
                 }
             }
         }
-        CoCourse(code) => {
-            if let Some(&co_idx) = idx_map.get(code) {
-                for s in 0..num_semesters {
-                    let cur = ctx.vars[course_idx][s];
-                    let upto_vars: Vec<_> = ctx.vars[co_idx][..=s].to_vec();
-                    if !upto_vars.is_empty() {
-                        let sum_upto: LinearExpr = upto_vars.into_iter().collect();
-                        ctx.model
-                            .add_linear_constraint(sum_upto - cur, [(0, i64::MAX)]);
-                    } else {
-                        ctx.model.add_eq(cur, 0);
-                    }
-                }
-            } else {
-                for s in 0..num_semesters {
-                    ctx.model.add_eq(ctx.vars[course_idx][s], 0);
-                }
+        // Resolved above
+        PreCourseGrade(..) | CoCourseGrade(..) | Instructor | Program(_) | TestScore(..)
+        | CreditsEarned(_) => unreachable!(),
+    }
+}
+
+fn add_prereq_for_course<'a>(
+    ctx: &mut ModelBuilderContext<'a>,
+    idx_map: &HashMap<CourseCode, usize>,
+    course_idx: usize,
+    req: &CourseReq,
+) {
+    // If the target course is an incoming course, skip prereq constraints entirely.
+    // Incoming courses are allowed in semester 0 and should not be blocked by prereqs.
+    if ctx.incoming_codes.contains(&ctx.courses[course_idx].code) {
+        return;
+    }
+    for s in 0..ctx.num_semesters {
+        let cur = ctx.vars[course_idx][s];
+        match reify_prereq(ctx, idx_map, course_idx, req, s) {
+            ReqLit::True => {}
+            ReqLit::False => {
+                ctx.model.add_eq(cur, 0);
+            }
+            ReqLit::Var(holds) => {
+                ctx.model.add_le(cur, holds);
             }
         }
-        _ => unimplemented!("Only PreCourse, CoCourse, And, Or supported"),
     }
 }
@@ -5,13 +5,21 @@ pub fn add_semester_constraints<'a>(ctx: &mut ModelBuilderContext<'a>) {
     // For each semester, sum the credits of all courses scheduled and add a constraint
     // Ignore semester 0 for max credits constraint
     for s in 1..ctx.num_semesters {
-        let weighted_terms: Vec<(i64, _)> = ctx
-            .courses
-            .iter()
-            .enumerate()
-            .map(|(i, c)| (c.credits, ctx.vars[i][s]))
-            .collect();
-        let weighted_sum: cp_sat::builder::LinearExpr = weighted_terms.into_iter().collect();
-        ctx.model.add_le(weighted_sum, ctx.max_credits_per_semester);
+        let mut weighted_sum = cp_sat::builder::LinearExpr::from(0);
+        for i in 0..ctx.courses.len() {
+            weighted_sum += ctx.credits_expr(i, s);
+        }
+        if ctx.is_summer(s) {
+            ctx.model
+                .add_le(weighted_sum, ctx.summer.max_credits as i64);
+            // A summer term counts as used as soon as any course is placed in it
+            let used = ctx.model.new_bool_var_with_name(format!("summer_{s}"));
+            for i in 0..ctx.courses.len() {
+                ctx.model.add_le(ctx.vars[i][s], used);
+            }
+            ctx.summer_used.push(used);
+        } else {
+            ctx.model.add_le(weighted_sum, ctx.max_credits_per_semester);
+        }
     }
 }
@@ -41,7 +41,7 @@ fn first_stage_sched(sched: &mut Schedule, max_credits_per_semester: i64) -> Res
     sched_for_model.courses = all_semesters;
     // Stage 1: minimize total credits
     let mut ctx = ModelBuilderContext::new(&sched_for_model, max_credits_per_semester);
-    let (mut model, vars, flat_courses) = build_model_pipeline(&mut ctx);
+    let (mut model, _vars, flat_courses) = build_model_pipeline(&mut ctx);
     // Determine number of semesters (includes incoming semester 0)
     let num_semesters: usize = sched_for_model.courses.len();
     let first_sched_semester = 1; // semester 0 is incoming only
@@ -49,8 +49,7 @@ fn first_stage_sched(sched: &mut Schedule, max_credits_per_semester: i64) -> Res
     let mut total_credits_sched = cp_sat::builder::LinearExpr::from(0);
     for s in first_sched_semester..num_semesters {
         for i in 0..flat_courses.len() {
-            let credits = flat_courses[i].1;
-            total_credits_sched += (credits, vars[i][s]);
+            total_credits_sched += ctx.credits_expr(i, s);
         }
     }
     model.minimize(total_credits_sched.clone());
@@ -60,19 +59,28 @@ fn first_stage_sched(sched: &mut Schedule, max_credits_per_semester: i64) -> Res
     let min_credits = match response.status() {
         CpSolverStatus::Optimal | CpSolverStatus::Feasible => {
             let mut total = 0;
-            for (i, (_course, credits)) in flat_courses.iter().enumerate() {
+            for i in 0..flat_courses.len() {
                 for s in first_sched_semester..num_semesters {
-                    if vars[i][s].solution_value(&response) {
-                        total += credits;
-                    }
+                    total += ctx.solution_credits(&response, i, s);
                 }
             }
             total
         }
         _ => {
-            // No feasible solution
+            // No feasible solution; a missed grade gate is the likely cause if there is one
+            let notes: String = ctx
+                .failed_gates
+                .iter()
+                .map(|gate| {
+                    format!(
+                        "; {} needs {} with {} or better, but the transcript shows {} and it \
+                         could not be retaken in time",
+                        gate.course, gate.prereq, gate.min, gate.grade
+                    )
+                })
+                .collect();
             return Err(anyhow!(
-                "No feasible solution found in single-stage scheduling"
+                "No feasible solution found in single-stage scheduling{notes}"
             ));
         }
     };
@@ -105,31 +113,47 @@ fn second_stage_sched(
     let mut ctx2 = ModelBuilderContext::new(&sched_for_model, max_credits_per_semester);
     ctx2.set_min_credits(min_credits);
     let (mut model2, vars2, flat_courses2) = build_model_pipeline(&mut ctx2);
-    // Compute mean load (rounded down), EXCLUDING semester 0 (incoming)
-    let num_sched_semesters = num_semesters as i64 - 1;
-    let mean_load = if num_sched_semesters > 0 {
-        min_credits / num_sched_semesters
-    } else {
-        0
-    };
+    // Regular semesters are balanced around their mean load, EXCLUDING semester 0 (incoming) and
+    // summer terms
+    let regular_semesters: Vec<usize> = (first_sched_semester..num_semesters)
+        .filter(|&s| !ctx2.is_summer(s))
+        .collect();
+    let num_sched_semesters = regular_semesters.len() as i64;
+    let max_load = max_credits_per_semester * flat_courses2.len() as i64;
 
-    // For each semester, create an IntVar for the semester's total credits
+    // For each regular semester, create an IntVar for the semester's total credits
     let mut semester_credit_vars = Vec::new();
-    for s in first_sched_semester..num_semesters {
+    let mut regular_credits = LinearExpr::from(0);
+    for &s in &regular_semesters {
         let mut expr = LinearExpr::from(0);
         for i in 0..flat_courses2.len() {
-            let var = vars2[i][s];
-            let coeff = flat_courses2[i].1;
-            // Use a single scaled addition (supports negative coeffs) instead of repeated adds/subtracts
-            expr += (coeff, var);
+            expr += ctx2.credits_expr(i, s);
         }
         // Domain: [0, max_credits_per_semester * flat_courses2.len() as i64]
-        let domain = vec![(0, max_credits_per_semester * flat_courses2.len() as i64)];
+        let domain = vec![(0, max_load)];
         let var = model2.new_int_var(domain.clone());
         model2.add_eq(var, expr);
+        regular_credits += var;
         semester_credit_vars.push(var);
     }
 
+    // Mean load (rounded down) of the regular semesters. Credits taken in summer terms are left out, so
+    // they lighten the regular semesters rather than raising their target.
+    let mean_load = model2.new_int_var(vec![(0, max_load)]);
+    if num_sched_semesters > 0 {
+        // n * mean <= credits <= n * mean + n - 1
+        model2.add_le(
+            LinearExpr::from((num_sched_semesters, mean_load)),
+            regular_credits.clone(),
+        );
+        model2.add_le(
+            regular_credits,
+            LinearExpr::from((num_sched_semesters, mean_load)) + (num_sched_semesters - 1),
+        );
+    } else {
+        model2.add_eq(mean_load, 0);
+    }
+
     // For each semester, create an IntVar for the absolute deviation from mean
     let mut abs_deviation_vars = Vec::new();
     for credit_var in semester_credit_vars.iter() {
@@ -139,7 +163,10 @@ fn second_stage_sched(
         )];
         let diff = model2.new_int_var(diff_domain);
         // diff = semester_credits - mean_load
-        model2.add_eq(diff, LinearExpr::from(*credit_var) - mean_load);
+        model2.add_eq(
+            diff,
+            LinearExpr::from(*credit_var) - LinearExpr::from(mean_load),
+        );
         let abs_domain = vec![(0, max_credits_per_semester * flat_courses2.len() as i64)];
         let abs_diff = model2.new_int_var(abs_domain);
         // abs_diff >= diff
@@ -158,6 +185,8 @@ fn second_stage_sched(
     for v in &abs_deviation_vars {
         spread_penalty += LinearExpr::from(*v);
     }
+    // Using a summer term costs as much as that many credits of imbalance
+    spread_penalty += ctx2.summer_cost_expr();
 
     // --- Ordering objective: penalize semesters where mean course code does not increase ---
     let mut sum_codes = Vec::new();
@@ -206,21 +235,64 @@ fn second_stage_sched(
         CpSolverStatus::Optimal | CpSolverStatus::Feasible => {
             // Build the schedule output: Vec<Vec<(CourseCode, i64)>>
             let mut result = vec![vec![]; num_semesters];
-            for (i, (course, credits)) in flat_courses2.iter().enumerate() {
+            for (i, (course, _)) in flat_courses2.iter().enumerate() {
                 for (s, item) in result.iter_mut().enumerate().take(num_semesters) {
                     if vars2[i][s].solution_value(&response2) {
-                        item.push((course.code.clone(), *credits));
+                        item.push((course.code.clone(), ctx2.solution_credits(&response2, i, s)));
                     }
                 }
             }
 
+            // Record the credits chosen for variable-credit courses. Incoming courses keep the credits
+            // the student already has on record.
+            let incoming_credits: Vec<_> = sched
+                .credits
+                .iter()
+                .filter(|(code, _)| sched.incoming.contains(code))
+                .map(|(code, credits)| (code.clone(), *credits))
+                .collect();
+            let credits = result
+                .iter()
+                .skip(first_sched_semester)
+                .flatten()
+                .filter(|(code, _)| {
+                    sched
+                        .catalog
+                        .courses
+                        .get(code)
+                        .and_then(|info| info.credits)
+                        .is_some_and(|credits| credits.is_variable())
+                })
+                .map(|(code, credits)| (code.clone(), *credits as u32))
+                .chain(incoming_credits)
+                .collect();
+
             // Strictly separate incoming (semester 0) from planned semesters (1..N)
             // Only planned semesters (1..N) go into sched.courses
-            sched.courses = result
+            let courses = result
                 .iter()
                 .skip(first_sched_semester)
                 .map(|sem| sem.iter().map(|(code, _)| code.clone()).collect())
                 .collect();
+            let previous = (
+                std::mem::replace(&mut sched.courses, courses),
+                std::mem::replace(&mut sched.credits, credits),
+            );
+
+            // Cross-check the prereq encoding against the prereq checker, keeping the old plan if
+            // they disagree
+            let unmet: Vec<_> = sched
+                .unmet_prereqs()
+                .iter()
+                .map(|(sem_idx, code)| format!("{code} in {}", sched.term(*sem_idx)))
+                .collect();
+            if !unmet.is_empty() {
+                (sched.courses, sched.credits) = previous;
+                return Err(anyhow!(
+                    "Solved schedule does not meet the prerequisites of {}",
+                    unmet.join(", ")
+                ));
+            }
             Ok(())
         }
         _ => Err(anyhow!(
@@ -0,0 +1,409 @@
+//! The prerequisite structure of the catalog as a directed graph, exported as Graphviz DOT or Mermaid.
+//!
+//! Edges run from a prerequisite to the course requiring it. Alternatives (`Or`) are drawn through a
+//! "one of" junction node, and `And`s nested inside them through an "all of" junction.
+use anyhow::{Result, bail};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::prereqs::{CourseReq, Grade};
+use crate::schedule::{Catalog, CourseCode};
+
+/// Which part of the catalog to draw.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum GraphScope {
+    Catalog,
+    /// The courses and elective options of the named program
+    Program(String),
+    /// Every course with the given stem
+    Stem(String),
+    /// A course and everything it transitively depends on
+    Closure(CourseCode),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GraphNode {
+    Course(CourseCode),
+    /// Index into [`PrereqGraph::groups`]
+    Group(usize),
+    /// Index into [`PrereqGraph::conditions`]
+    Condition(usize),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GraphCourse {
+    pub code: CourseCode,
+    pub title: Option<String>,
+    /// Whether the course's own requirements are drawn; prerequisites from outside the scope are not
+    pub in_scope: bool,
+}
+
+/// A junction joining the alternatives of an `Or` (or the parts of an `And` nested in one).
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReqGroup {
+    /// One of the incoming requirements suffices
+    pub any: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// Taken in an earlier term
+    Pre,
+    /// Taken in an earlier or the same term
+    Co,
+    /// Taken in the same term
+    SameTerm,
+    /// From a junction or condition to what it belongs to
+    Requires,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PrereqEdge {
+    pub from: GraphNode,
+    pub to: GraphNode,
+    pub kind: EdgeKind,
+    pub grade: Option<Grade>,
+}
+
+impl PrereqEdge {
+    pub fn label(&self) -> Option<String> {
+        let kind = match self.kind {
+            EdgeKind::Pre => "pre",
+            EdgeKind::Co => "co",
+            EdgeKind::SameTerm => "same term",
+            EdgeKind::Requires => return None,
+        };
+        Some(match &self.grade {
+            Some(grade) => format!("{kind}, {grade} or better"),
+            None => kind.to_string(),
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct PrereqGraph {
+    /// Sorted by course code
+    pub courses: Vec<GraphCourse>,
+    pub groups: Vec<ReqGroup>,
+    /// Requirements that are not courses, such as standing or instructor consent
+    pub conditions: Vec<String>,
+    pub edges: Vec<PrereqEdge>,
+}
+
+fn course_leaves<'a>(req: &'a CourseReq, codes: &mut Vec<&'a CourseCode>) {
+    match req {
+        CourseReq::And(reqs) | CourseReq::Or(reqs) => {
+            for r in reqs {
+                course_leaves(r, codes);
+            }
+        }
+        CourseReq::PreCourse(code)
+        | CourseReq::CoCourse(code)
+        | CourseReq::PreCourseGrade(code, _)
+        | CourseReq::CoCourseGrade(code, _)
+        | CourseReq::SameTermCourse(code) => codes.push(code),
+        _ => {}
+    }
+}
+
+struct GraphBuilder {
+    graph: PrereqGraph,
+    courses: HashMap<CourseCode, bool>,
+}
+
+impl GraphBuilder {
+    fn edge(&mut self, from: GraphNode, to: &GraphNode, kind: EdgeKind, grade: Option<&Grade>) {
+        self.graph.edges.push(PrereqEdge {
+            from,
+            to: to.clone(),
+            kind,
+            grade: grade.cloned(),
+        });
+    }
+
+    fn course_edge(
+        &mut self,
+        code: &CourseCode,
+        to: &GraphNode,
+        kind: EdgeKind,
+        grade: Option<&Grade>,
+    ) {
+        self.courses.entry(code.clone()).or_insert(false);
+        self.edge(GraphNode::Course(code.clone()), to, kind, grade);
+    }
+
+    fn group(&mut self, any: bool, to: &GraphNode) -> GraphNode {
+        self.graph.groups.push(ReqGroup { any });
+        let group = GraphNode::Group(self.graph.groups.len() - 1);
+        self.edge(group.clone(), to, EdgeKind::Requires, None);
+        group
+    }
+
+    // `in_any` is set while adding the alternatives of an `Or` junction
+    fn add_req(&mut self, req: &CourseReq, to: &GraphNode, in_any: bool) {
+        match req {
+            CourseReq::And(reqs) | CourseReq::Or(reqs) => {
+                let any = matches!(req, CourseReq::Or(_));
+                let to = if any == in_any {
+                    to.clone()
+                } else {
+                    self.group(any, to)
+                };
+                for r in reqs {
+                    self.add_req(r, &to, any);
+                }
+            }
+            CourseReq::PreCourse(code) => self.course_edge(code, to, EdgeKind::Pre, None),
+            CourseReq::CoCourse(code) => self.course_edge(code, to, EdgeKind::Co, None),
+            CourseReq::SameTermCourse(code) => self.course_edge(code, to, EdgeKind::SameTerm, None),
+            CourseReq::PreCourseGrade(code, grade) => {
+                self.course_edge(code, to, EdgeKind::Pre, Some(grade))
+            }
+            CourseReq::CoCourseGrade(code, grade) => {
+                self.course_edge(code, to, EdgeKind::Co, Some(grade))
+            }
+            CourseReq::NotRequired => {}
+            CourseReq::Program(_)
+            | CourseReq::Standing(_)
+            | CourseReq::CreditsEarned(_)
+            | CourseReq::TestScore(..)
+            | CourseReq::Instructor => {
+                let label = req.to_string();
+                let idx = match self.graph.conditions.iter().position(|c| *c == label) {
+                    Some(idx) => idx,
+                    None => {
+                        self.graph.conditions.push(label);
+                        self.graph.conditions.len() - 1
+                    }
+                };
+                self.edge(GraphNode::Condition(idx), to, EdgeKind::Requires, None);
+            }
+        }
+    }
+}
+
+impl Catalog {
+    /// The prerequisite graph of the courses in `scope`, together with the prerequisites they name.
+    pub fn prereq_graph(&self, scope: &GraphScope) -> Result<PrereqGraph> {
+        let focus: Vec<CourseCode> = match scope {
+            GraphScope::Catalog => self
+                .courses
+                .keys()
+                .chain(self.prereqs.keys())
+                .cloned()
+                .collect(),
+            GraphScope::Program(name) => {
+                let Some(prog) = self.programs.iter().find(|p| p.name == *name) else {
+                    bail!("Program {name} not found in catalog");
+                };
+                prog.semesters
+                    .iter()
+                    .flatten()
+                    .cloned()
+                    .chain(prog.electives.iter().flat_map(|e| e.req.all_course_codes()))
+                    .collect()
+            }
+            GraphScope::Stem(stem) => self
+                .courses
+                .keys()
+                .chain(self.prereqs.keys())
+                .filter(|code| code.stem == *stem)
+                .cloned()
+                .collect(),
+            GraphScope::Closure(code) => {
+                if !self.courses.contains_key(code) && !self.prereqs.contains_key(code) {
+                    bail!("Course {code} not found in catalog");
+                }
+                let mut seen = vec![code.clone()];
+                let mut idx = 0;
+                while let Some(code) = seen.get(idx) {
+                    if let Some(req) = self.prereqs.get(code) {
+                        let mut leaves = Vec::new();
+                        course_leaves(req, &mut leaves);
+                        for leaf in leaves {
+                            if !seen.contains(leaf) {
+                                seen.push(leaf.clone());
+                            }
+                        }
+                    }
+                    idx += 1;
+                }
+                seen
+            }
+        };
+
+        let mut builder = GraphBuilder {
+            graph: PrereqGraph::default(),
+            courses: focus.iter().map(|code| (code.clone(), true)).collect(),
+        };
+        let mut focus = focus;
+        focus.sort_by_key(|c| c.to_string());
+        focus.dedup();
+        for code in &focus {
+            if let Some(req) = self.prereqs.get(code) {
+                builder.add_req(req, &GraphNode::Course(code.clone()), false);
+            }
+        }
+
+        let GraphBuilder { mut graph, courses } = builder;
+        graph.courses = courses
+            .into_iter()
+            .map(|(code, in_scope)| GraphCourse {
+                title: self.courses.get(&code).map(|info| info.title.clone()),
+                code,
+                in_scope,
+            })
+            .collect();
+        graph.courses.sort_by_key(|c| c.code.to_string());
+        Ok(graph)
+    }
+}
+
+// Node ids valid in both DOT and Mermaid
+fn node_id(node: &GraphNode) -> String {
+    match node {
+        GraphNode::Course(code) => code
+            .to_string()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect(),
+        GraphNode::Group(idx) => format!("group{idx}"),
+        GraphNode::Condition(idx) => format!("cond{idx}"),
+    }
+}
+
+fn group_label(group: &ReqGroup) -> &'static str {
+    if group.any { "one of" } else { "all of" }
+}
+
+impl PrereqGraph {
+    fn course_label(
+        course: &GraphCourse,
+        line_break: &str,
+        escape: impl Fn(&str) -> String,
+    ) -> String {
+        match &course.title {
+            Some(title) => format!("{}{line_break}{}", course.code, escape(title)),
+            None => course.code.to_string(),
+        }
+    }
+
+    /// The graph in Graphviz DOT. Courses outside the scope are dashed, as are corequisite edges;
+    /// same-term edges are bold.
+    pub fn to_dot(&self) -> String {
+        let escape = |s: &str| s.replace('\\', "\\\\").replace('"', "\\\"");
+        let mut out = String::from("digraph prereqs {\n    rankdir=LR;\n    node [shape=box];\n");
+        for course in &self.courses {
+            let style = if course.in_scope {
+                ""
+            } else {
+                ", style=dashed"
+            };
+            let _ = writeln!(
+                out,
+                "    {} [label=\"{}\"{style}];",
+                node_id(&GraphNode::Course(course.code.clone())),
+                Self::course_label(course, "\\n", escape)
+            );
+        }
+        for (idx, group) in self.groups.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    {} [label=\"{}\", shape=diamond];",
+                node_id(&GraphNode::Group(idx)),
+                group_label(group)
+            );
+        }
+        for (idx, condition) in self.conditions.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    {} [label=\"{}\", shape=note];",
+                node_id(&GraphNode::Condition(idx)),
+                escape(condition)
+            );
+        }
+        for edge in &self.edges {
+            let mut attrs = Vec::new();
+            if let Some(label) = edge.label() {
+                attrs.push(format!("label=\"{label}\""));
+            }
+            match edge.kind {
+                EdgeKind::Co => attrs.push("style=dashed".to_string()),
+                EdgeKind::SameTerm => attrs.push("style=bold".to_string()),
+                _ => {}
+            }
+            let attrs = if attrs.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", attrs.join(", "))
+            };
+            let _ = writeln!(
+                out,
+                "    {} -> {}{attrs};",
+                node_id(&edge.from),
+                node_id(&edge.to)
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// The graph as a Mermaid flowchart. Corequisite edges are dotted, same-term edges thick.
+    pub fn to_mermaid(&self) -> String {
+        let escape = |s: &str| s.replace('"', "#quot;");
+        let mut out = String::from("flowchart LR\n");
+        for course in &self.courses {
+            let _ = writeln!(
+                out,
+                "    {}[\"{}\"]",
+                node_id(&GraphNode::Course(course.code.clone())),
+                Self::course_label(course, "<br/>", escape)
+            );
+        }
+        for (idx, group) in self.groups.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    {}{{\"{}\"}}",
+                node_id(&GraphNode::Group(idx)),
+                group_label(group)
+            );
+        }
+        for (idx, condition) in self.conditions.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "    {}>\"{}\"]",
+                node_id(&GraphNode::Condition(idx)),
+                escape(condition)
+            );
+        }
+        for edge in &self.edges {
+            let arrow = match edge.kind {
+                EdgeKind::Co => "-.->",
+                EdgeKind::SameTerm => "==>",
+                _ => "-->",
+            };
+            let label = edge
+                .label()
+                .map(|label| format!("|{label}|"))
+                .unwrap_or_default();
+            let _ = writeln!(
+                out,
+                "    {} {arrow}{label} {}",
+                node_id(&edge.from),
+                node_id(&edge.to)
+            );
+        }
+        let outside: Vec<String> = self
+            .courses
+            .iter()
+            .filter(|c| !c.in_scope)
+            .map(|c| node_id(&GraphNode::Course(c.code.clone())))
+            .collect();
+        if !outside.is_empty() {
+            out.push_str("    classDef outside stroke-dasharray: 5 5\n");
+            let _ = writeln!(out, "    class {} outside", outside.join(","));
+        }
+        out
+    }
+}
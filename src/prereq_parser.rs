@@ -0,0 +1,759 @@
+//! Turn catalog prerequisite prose into [`CourseReq`] trees.
+//!
+//! Understands the phrasing used in course descriptions, e.g. "CHEM-1210 and (MATH-1350 or MATH-1300
+//! with a grade of C or better), concurrent enrollment in CHEM-3301", as well as the text [`CourseReq`]
+//! is displayed as. `and` binds looser than `or`; commas and semicolons separate requirements that all
+//! apply, unless the list reads "A, B, or C".
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::prereqs::{CourseReq, Grade, GradeLetter, GradeQualifier, Standing};
+use crate::schedule::{CourseCode, CourseCodeSuffix};
+
+/// Why (and where) prerequisite text could not be parsed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PrereqDiagnostic {
+    pub message: String,
+    /// Byte offset into the text where parsing stopped
+    pub offset: usize,
+    pub text: String,
+}
+
+impl fmt::Display for PrereqDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rest = &self.text[self.offset..];
+        let excerpt: String = rest.chars().take(30).collect();
+        if excerpt.is_empty() {
+            write!(f, "{} at the end of \"{}\"", self.message, self.text)
+        } else if excerpt.len() < rest.len() {
+            write!(f, "{} at \"{excerpt}...\"", self.message)
+        } else {
+            write!(f, "{} at \"{excerpt}\"", self.message)
+        }
+    }
+}
+
+impl std::error::Error for PrereqDiagnostic {}
+
+/// Parse the prerequisite text of a course. Bare course numbers ("CHEM-1210 or 1220") take the stem of
+/// the last course mentioned, or `default_stem` (usually the course's own stem) before any.
+pub fn parse_prereqs(text: &str, default_stem: &str) -> Result<CourseReq, PrereqDiagnostic> {
+    let toks = tokenize(text)?;
+    Parser {
+        text,
+        toks,
+        pos: 0,
+        stem: default_stem.to_ascii_uppercase(),
+        default_stem: default_stem.to_ascii_uppercase(),
+    }
+    .parse()
+}
+
+/// Parse the prerequisite text of many courses, each with its own stem as the default stem. Returns
+/// the parsed requirements and a diagnostic for each text that could not be parsed.
+pub fn parse_prereq_texts<'a>(
+    texts: impl IntoIterator<Item = (CourseCode, &'a str)>,
+) -> (
+    HashMap<CourseCode, CourseReq>,
+    Vec<(CourseCode, PrereqDiagnostic)>,
+) {
+    let mut prereqs = HashMap::new();
+    let mut diagnostics = Vec::new();
+    for (code, text) in texts {
+        match parse_prereqs(text, &code.stem) {
+            Ok(req) => {
+                prereqs.insert(code, req);
+            }
+            Err(diagnostic) => diagnostics.push((code, diagnostic)),
+        }
+    }
+    (prereqs, diagnostics)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Tok {
+    Course(CourseCode),
+    Grade(Grade),
+    Num(u32),
+    /// Any other word, as written
+    Word(String),
+    LParen,
+    RParen,
+    /// `,`, `;` or `.`
+    Sep,
+    Colon,
+    Slash,
+    /// `>=` or `≥`
+    AtLeast,
+}
+
+fn is_stem(word: &str) -> bool {
+    (2..=4).contains(&word.len()) && word.chars().all(|c| c.is_ascii_uppercase())
+}
+
+fn grade_letter(word: &str) -> Option<GradeLetter> {
+    match word {
+        "A" => Some(GradeLetter::A),
+        "B" => Some(GradeLetter::B),
+        "C" => Some(GradeLetter::C),
+        "D" => Some(GradeLetter::D),
+        "F" => Some(GradeLetter::F),
+        _ => None,
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Tok)>, PrereqDiagnostic> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let char_at = |i: usize| chars.get(i).map(|&(_, c)| c);
+    let is_word_char = |c: char| c.is_ascii_alphanumeric() || c == '\'' || c == '’';
+    let mut toks = Vec::new();
+    let mut i = 0;
+    while let Some(&(offset, c)) = chars.get(i) {
+        let simple = match c {
+            '(' | '[' => Some(Tok::LParen),
+            ')' | ']' => Some(Tok::RParen),
+            ',' | ';' | '.' => Some(Tok::Sep),
+            ':' => Some(Tok::Colon),
+            '/' => Some(Tok::Slash),
+            '≥' => Some(Tok::AtLeast),
+            _ => None,
+        };
+        if let Some(tok) = simple {
+            toks.push((offset, tok));
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '>' && char_at(i + 1) == Some('=') {
+            toks.push((offset, Tok::AtLeast));
+            i += 2;
+            continue;
+        }
+        if !c.is_ascii_alphanumeric() {
+            return Err(PrereqDiagnostic {
+                message: format!("unexpected character '{c}'"),
+                offset,
+                text: text.to_string(),
+            });
+        }
+
+        let start = i;
+        while char_at(i).is_some_and(is_word_char) {
+            i += 1;
+        }
+        let word: String = chars[start..i].iter().map(|&(_, c)| c).collect();
+
+        // Course codes: STEM-1210, STEM 1210 or STEM-COMP
+        if is_stem(&word)
+            && let Some(sep @ ('-' | ' ')) = char_at(i)
+            && char_at(i + 1).is_some_and(|c| c.is_ascii_alphanumeric())
+        {
+            let mut end = i + 1;
+            while char_at(end).is_some_and(|c| c.is_ascii_alphanumeric()) {
+                end += 1;
+            }
+            let suffix: String = chars[i + 1..end].iter().map(|&(_, c)| c).collect();
+            let code = match suffix.parse::<usize>() {
+                Ok(num) if sep == '-' || suffix.len() == 4 => Some(CourseCodeSuffix::Number(num)),
+                Err(_) if sep == '-' && suffix.chars().all(|c| c.is_ascii_uppercase()) => {
+                    Some(CourseCodeSuffix::Special(suffix))
+                }
+                _ => None,
+            };
+            if let Some(code) = code {
+                toks.push((offset, Tok::Course(CourseCode { stem: word, code })));
+                i = end;
+                continue;
+            }
+        }
+
+        if let Some(letter) = grade_letter(&word) {
+            let qualifier = match char_at(i) {
+                Some('+') => GradeQualifier::Plus,
+                Some('-') => GradeQualifier::Minus,
+                _ => GradeQualifier::None,
+            };
+            if qualifier != GradeQualifier::None {
+                i += 1;
+            }
+            toks.push((offset, Tok::Grade(Grade { letter, qualifier })));
+        } else if word.chars().all(|c| c.is_ascii_digit()) {
+            let num = word.parse().map_err(|_| PrereqDiagnostic {
+                message: format!("number {word} is too large"),
+                offset,
+                text: text.to_string(),
+            })?;
+            toks.push((offset, Tok::Num(num)));
+        } else {
+            toks.push((offset, Tok::Word(word)));
+        }
+    }
+    Ok(toks)
+}
+
+/// All of `reqs`, with nested `And`s flattened.
+fn all_of(reqs: Vec<CourseReq>) -> CourseReq {
+    let mut flat = Vec::new();
+    for req in reqs {
+        match req {
+            CourseReq::And(inner) => flat.extend(inner),
+            req => flat.push(req),
+        }
+    }
+    if flat.len() == 1 {
+        flat.remove(0)
+    } else {
+        CourseReq::And(flat)
+    }
+}
+
+/// One of `reqs`, with nested `Or`s flattened.
+fn one_of(reqs: Vec<CourseReq>) -> CourseReq {
+    let mut flat = Vec::new();
+    for req in reqs {
+        match req {
+            CourseReq::Or(inner) => flat.extend(inner),
+            req => flat.push(req),
+        }
+    }
+    if flat.len() == 1 {
+        flat.remove(0)
+    } else {
+        CourseReq::Or(flat)
+    }
+}
+
+/// Let every course in `req` be taken concurrently.
+fn concurrent(req: CourseReq) -> CourseReq {
+    match req {
+        CourseReq::And(reqs) => CourseReq::And(reqs.into_iter().map(concurrent).collect()),
+        CourseReq::Or(reqs) => CourseReq::Or(reqs.into_iter().map(concurrent).collect()),
+        CourseReq::PreCourse(code) => CourseReq::CoCourse(code),
+        CourseReq::PreCourseGrade(code, grade) => CourseReq::CoCourseGrade(code, grade),
+        req => req,
+    }
+}
+
+/// Require every course in `req` to be taken in the same term. `None` if `req` asks for a minimum
+/// grade, which a course taken in the same term has not earned yet.
+fn same_term(req: CourseReq) -> Option<CourseReq> {
+    match req {
+        CourseReq::And(reqs) => reqs
+            .into_iter()
+            .map(same_term)
+            .collect::<Option<_>>()
+            .map(CourseReq::And),
+        CourseReq::Or(reqs) => reqs
+            .into_iter()
+            .map(same_term)
+            .collect::<Option<_>>()
+            .map(CourseReq::Or),
+        CourseReq::PreCourse(code) | CourseReq::CoCourse(code) => {
+            Some(CourseReq::SameTermCourse(code))
+        }
+        CourseReq::PreCourseGrade(..) | CourseReq::CoCourseGrade(..) => None,
+        req => Some(req),
+    }
+}
+
+const SAME_TERM_GRADE: &str =
+    "a minimum grade cannot be required of a course taken in the same term";
+
+/// Require a minimum grade in every course in `req`.
+fn with_grade(req: CourseReq, grade: &Grade) -> CourseReq {
+    match req {
+        CourseReq::And(reqs) => {
+            CourseReq::And(reqs.into_iter().map(|r| with_grade(r, grade)).collect())
+        }
+        CourseReq::Or(reqs) => {
+            CourseReq::Or(reqs.into_iter().map(|r| with_grade(r, grade)).collect())
+        }
+        CourseReq::PreCourse(code) | CourseReq::PreCourseGrade(code, _) => {
+            CourseReq::PreCourseGrade(code, grade.clone())
+        }
+        CourseReq::CoCourse(code) | CourseReq::CoCourseGrade(code, _) => {
+            CourseReq::CoCourseGrade(code, grade.clone())
+        }
+        req => req,
+    }
+}
+
+/// When the courses a qualifier follows may be taken.
+enum Timing {
+    Concurrent,
+    SameTerm,
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    toks: Vec<(usize, Tok)>,
+    pos: usize,
+    /// Stem for bare course numbers
+    stem: String,
+    default_stem: String,
+}
+
+impl Parser<'_> {
+    fn peek_at(&self, n: usize) -> Option<&Tok> {
+        self.toks.get(self.pos + n).map(|(_, tok)| tok)
+    }
+
+    fn peek(&self) -> Option<&Tok> {
+        self.peek_at(0)
+    }
+
+    fn at_end(&self) -> bool {
+        self.pos >= self.toks.len()
+    }
+
+    fn error(&self, message: impl Into<String>) -> PrereqDiagnostic {
+        self.error_at(self.pos, message)
+    }
+
+    /// An error at the token `pos`, for a problem only found after parsing past it
+    fn error_at(&self, pos: usize, message: impl Into<String>) -> PrereqDiagnostic {
+        PrereqDiagnostic {
+            message: message.into(),
+            offset: self
+                .toks
+                .get(pos)
+                .map_or(self.text.len(), |&(offset, _)| offset),
+            text: self.text.to_string(),
+        }
+    }
+
+    fn is_word_at(&self, n: usize, word: &str) -> bool {
+        matches!(self.peek_at(n), Some(Tok::Word(w)) if w.eq_ignore_ascii_case(word))
+    }
+
+    fn eat(&mut self, tok: &Tok) -> bool {
+        if self.peek() == Some(tok) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_word(&mut self, word: &str) -> bool {
+        self.eat_words(&[word])
+    }
+
+    /// Consume `words` if the text continues with exactly these words.
+    fn eat_words(&mut self, words: &[&str]) -> bool {
+        if words.iter().enumerate().all(|(n, w)| self.is_word_at(n, w)) {
+            self.pos += words.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Consume one of the `words`, returning which.
+    fn eat_any_word(&mut self, words: &[&'static str]) -> Option<&'static str> {
+        words.iter().copied().find(|w| self.eat_word(w))
+    }
+
+    fn eat_num(&mut self) -> Option<u32> {
+        match self.peek() {
+            Some(&Tok::Num(num)) => {
+                self.pos += 1;
+                Some(num)
+            }
+            _ => None,
+        }
+    }
+
+    fn eat_grade(&mut self) -> Option<Grade> {
+        match self.peek() {
+            Some(Tok::Grade(grade)) => {
+                let grade = grade.clone();
+                self.pos += 1;
+                Some(grade)
+            }
+            _ => None,
+        }
+    }
+
+    /// Run `f`, rewinding if it does not match.
+    fn attempt<T>(&mut self, f: impl FnOnce(&mut Self) -> Option<T>) -> Option<T> {
+        let pos = self.pos;
+        let result = f(self);
+        if result.is_none() {
+            self.pos = pos;
+        }
+        result
+    }
+
+    fn eat_or_better(&mut self) -> bool {
+        self.eat_words(&["or", "better"])
+            || self.eat_words(&["or", "higher"])
+            || self.eat_words(&["or", "above"])
+    }
+
+    fn parse(mut self) -> Result<CourseReq, PrereqDiagnostic> {
+        // An optional "Prerequisite(s):" or "Corequisite(s):" label
+        let label = self.attempt(|p| {
+            let label = p.eat_any_word(&[
+                "prerequisite",
+                "prerequisites",
+                "prereq",
+                "prereqs",
+                "corequisite",
+                "corequisites",
+                "coreq",
+                "coreqs",
+            ])?;
+            p.eat(&Tok::Colon).then_some(label)
+        });
+        while self.eat(&Tok::Sep) {}
+        if self.at_end() {
+            return Ok(CourseReq::NotRequired);
+        }
+        let req = self.list()?;
+        if !self.at_end() {
+            return Err(self.error("unexpected text"));
+        }
+        Ok(match label {
+            Some(label) if label.starts_with("co") => concurrent(req),
+            _ => req,
+        })
+    }
+
+    // Requirements separated by commas/semicolons; "A, B, or C" makes the list a choice
+    fn list(&mut self) -> Result<CourseReq, PrereqDiagnostic> {
+        let mut items = vec![self.conj()?];
+        let mut choice = false;
+        while self.eat(&Tok::Sep) {
+            while self.eat(&Tok::Sep) {}
+            if self.at_end() || self.peek() == Some(&Tok::RParen) {
+                break;
+            }
+            if self.eat_word("or") {
+                choice = true;
+            } else {
+                self.eat_word("and");
+            }
+            items.push(self.conj()?);
+        }
+        Ok(if choice { one_of(items) } else { all_of(items) })
+    }
+
+    fn conj(&mut self) -> Result<CourseReq, PrereqDiagnostic> {
+        let mut items = vec![self.disj()?];
+        while self.eat_word("and") {
+            items.push(self.disj()?);
+        }
+        Ok(all_of(items))
+    }
+
+    fn disj(&mut self) -> Result<CourseReq, PrereqDiagnostic> {
+        let mut items = vec![self.suffixed()?];
+        while self.eat_word("or") {
+            items.push(self.suffixed()?);
+        }
+        Ok(one_of(items))
+    }
+
+    /// A requirement followed by grade and concurrency qualifiers for its courses.
+    fn suffixed(&mut self) -> Result<CourseReq, PrereqDiagnostic> {
+        let start = self.pos;
+        let mut req = self.unit()?;
+        while let Some((grade, timing)) = self.suffix() {
+            if let Some(grade) = grade {
+                req = with_grade(req, &grade);
+            }
+            req = match timing {
+                Some(Timing::Concurrent) => concurrent(req),
+                Some(Timing::SameTerm) => {
+                    same_term(req).ok_or_else(|| self.error_at(start, SAME_TERM_GRADE))?
+                }
+                None => req,
+            };
+        }
+        Ok(req)
+    }
+
+    fn suffix(&mut self) -> Option<(Option<Grade>, Option<Timing>)> {
+        if let Some(grade) = self.grade_phrase() {
+            return Some((Some(grade), None));
+        }
+        if let Some(timing) = self.timing_phrase() {
+            return Some((None, Some(timing)));
+        }
+        // "(C or better)", "(or concurrently)", "(C or better, or concurrently)", "(same term)"
+        self.attempt(|p| {
+            if !p.eat(&Tok::LParen) {
+                return None;
+            }
+            let grade = p.grade_phrase();
+            p.eat(&Tok::Sep);
+            let timing = p.timing_phrase();
+            ((grade.is_some() || timing.is_some()) && p.eat(&Tok::RParen))
+                .then_some((grade, timing))
+        })
+    }
+
+    // "with a (minimum) grade of C (or better)", "with C or better", "C or better"
+    fn grade_phrase(&mut self) -> Option<Grade> {
+        self.attempt(|p| {
+            let mut introduced = false;
+            if p.eat_word("with") {
+                introduced = true;
+                let _ = p.eat_word("a") || p.eat_word("an");
+            }
+            p.eat_word("minimum");
+            if p.eat_word("grade") {
+                introduced = true;
+                p.eat_word("of");
+            }
+            let grade = p.eat_grade()?;
+            (p.eat_or_better() || introduced).then_some(grade)
+        })
+    }
+
+    // "concurrently", "or concurrently", "may be taken concurrently", "or concurrent enrollment";
+    // "same term", "must be taken in the same semester"
+    fn timing_phrase(&mut self) -> Option<Timing> {
+        if self.eat_words(&["may", "be", "taken", "concurrently"])
+            || self.eat_words(&["taken", "concurrently"])
+            || self.eat_words(&["or", "concurrently"])
+            || self.eat_word("concurrently")
+            || (!self.is_word_at(3, "in") && self.eat_words(&["or", "concurrent", "enrollment"]))
+        {
+            return Some(Timing::Concurrent);
+        }
+        self.attempt(|p| {
+            let _ = p.eat_words(&["must", "be", "taken"]) || p.eat_word("taken");
+            let _ = p.eat_words(&["in", "the"]);
+            p.eat_word("same").then_some(())?;
+            p.eat_any_word(&["term", "semester"])?;
+            Some(Timing::SameTerm)
+        })
+    }
+
+    fn course(&mut self, code: CourseCode) -> CourseReq {
+        self.stem = code.stem.clone();
+        CourseReq::PreCourse(code)
+    }
+
+    fn unit(&mut self) -> Result<CourseReq, PrereqDiagnostic> {
+        match self.peek().cloned() {
+            Some(Tok::LParen) => {
+                self.pos += 1;
+                let req = self.list()?;
+                if !self.eat(&Tok::RParen) {
+                    return Err(self.error("expected ')'"));
+                }
+                Ok(req)
+            }
+            Some(Tok::Course(code)) => {
+                self.pos += 1;
+                Ok(self.course(code))
+            }
+            Some(Tok::Num(_)) => self.numeric(),
+            Some(Tok::Word(_)) => self.phrase(),
+            Some(_) => Err(self.error("expected a course or requirement")),
+            None => Err(self.error("expected a course or requirement")),
+        }
+    }
+
+    // Credit counts ("60 credit hours", "30 to 59 credits earned") and bare course numbers
+    fn numeric(&mut self) -> Result<CourseReq, PrereqDiagnostic> {
+        if let Some(standing) = self.attempt(|p| {
+            let min = p.eat_num()?;
+            p.eat_word("to").then_some(())?;
+            let max = p.eat_num()?;
+            p.credits_words().then_some(Standing::between(min, max))
+        }) {
+            return Ok(CourseReq::Standing(standing));
+        }
+        if let Some(credits) = self.attempt(|p| {
+            let credits = p.eat_num()?;
+            p.credits_words().then_some(credits)
+        }) {
+            return Ok(CourseReq::CreditsEarned(credits));
+        }
+        match self.eat_num() {
+            Some(num) if (1000..=9999).contains(&num) => {
+                let code = CourseCode {
+                    stem: self.stem.clone(),
+                    code: CourseCodeSuffix::Number(num as usize),
+                };
+                Ok(self.course(code))
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.error("expected a course number or a number of credits"))
+            }
+        }
+    }
+
+    // "credits", "credit hours", "hours", optionally followed by "earned" or "completed"
+    fn credits_words(&mut self) -> bool {
+        let matched = self.eat_words(&["credit", "hours"])
+            || self.eat_words(&["semester", "hours"])
+            || self.eat_any_word(&["credits", "hours", "credit"]).is_some();
+        if matched {
+            let _ = self.eat_word("earned") || self.eat_word("completed");
+        }
+        matched
+    }
+
+    fn phrase(&mut self) -> Result<CourseReq, PrereqDiagnostic> {
+        if self.eat_word("none") {
+            return Ok(CourseReq::NotRequired);
+        }
+
+        // "concurrent enrollment in", "prior or concurrent enrollment in"
+        if self.eat_words(&["simultaneous", "enrollment", "in"]) {
+            let start = self.pos;
+            return same_term(self.disj()?).ok_or_else(|| self.error_at(start, SAME_TERM_GRADE));
+        }
+        if self.eat_words(&["prior", "or", "concurrent", "enrollment", "in"])
+            || self.eat_words(&["concurrent", "enrollment", "in"])
+            || self.eat_words(&["concurrent", "registration", "in"])
+        {
+            return Ok(concurrent(self.disj()?));
+        }
+
+        if self.eat_words(&["completion", "of"])
+            || self.eat_words(&["successful", "completion", "of"])
+        {
+            return self.suffixed();
+        }
+
+        if let Some(standing) = self.standing() {
+            return Ok(CourseReq::Standing(standing));
+        }
+
+        if self.consent() {
+            return Ok(CourseReq::Instructor);
+        }
+
+        // "placement into MATH-1300": no prereq records placements yet
+        if self.is_word_at(0, "placement") {
+            return Err(self.error("placement prerequisites are not supported"));
+        }
+
+        // "CHEM program", "CHEM majors only", "majors only"
+        if let Some(stem) = self.attempt(|p| {
+            let stem = match p.peek() {
+                Some(Tok::Word(w)) if is_stem(w) => {
+                    let stem = w.clone();
+                    p.pos += 1;
+                    stem
+                }
+                _ => p.default_stem.clone(),
+            };
+            p.eat_any_word(&["program", "majors", "major"])?;
+            p.eat_word("only");
+            Some(stem)
+        }) {
+            return Ok(CourseReq::Program(stem));
+        }
+
+        if let Some((test, min)) = self.test_score() {
+            return Ok(CourseReq::TestScore(test, min));
+        }
+
+        Err(self.error("unrecognized requirement"))
+    }
+
+    fn standing(&mut self) -> Option<Standing> {
+        self.attempt(|p| {
+            let standing = if let Some(class) = p.eat_any_word(&["sophomore", "junior", "senior"]) {
+                let _ = p.eat_word("class");
+                p.eat_any_word(&["standing", "status"])?;
+                p.eat_or_better();
+                match class {
+                    "sophomore" => Standing::sophomore(),
+                    "junior" => Standing::junior(),
+                    _ => Standing::senior(),
+                }
+            } else if p.eat_any_word(&["fr", "freshman", "freshmen"]).is_some() {
+                if !p.eat(&Tok::Slash) {
+                    p.eat_any_word(&["or", "and"])?;
+                }
+                p.eat_any_word(&["so", "sophomore", "sophomores"])?;
+                p.eat_any_word(&["standing", "status"]);
+                Standing::underclass()
+            } else if let Some(bound) = p
+                .eat_any_word(&["at"])
+                .and_then(|_| p.eat_any_word(&["least", "most"]))
+            {
+                let credits = p.eat_num()?;
+                p.credits_words().then_some(())?;
+                match bound {
+                    "least" => Standing::at_least(credits),
+                    _ => Standing::at_most(credits),
+                }
+            } else if p.eat_words(&["any", "standing"]) {
+                Standing {
+                    min_credits: None,
+                    max_credits: None,
+                }
+            } else {
+                return None;
+            };
+            p.eat_word("only");
+            Some(standing)
+        })
+    }
+
+    // "instructor consent", "consent of (the) instructor", "permission of department", ...
+    fn consent(&mut self) -> bool {
+        const WHO: &[&str] = &[
+            "instructor",
+            "instructor's",
+            "instructor’s",
+            "department",
+            "department's",
+            "department’s",
+            "chair",
+        ];
+        const WHAT: &[&str] = &["consent", "permission", "approval"];
+        self.attempt(|p| {
+            p.eat_word("the");
+            if p.eat_any_word(WHO).is_some() {
+                p.eat_any_word(WHAT)?;
+            } else {
+                p.eat_any_word(WHAT)?;
+                p.eat_word("of");
+                p.eat_word("the");
+                p.eat_any_word(WHO)?;
+            }
+            Some(())
+        })
+        .is_some()
+    }
+
+    // "ACT Math ≥ 24", "ACT Math score of 24 or higher", "SAT Math of 580"
+    fn test_score(&mut self) -> Option<(String, u32)> {
+        self.attempt(|p| {
+            let mut name = Vec::new();
+            while let Some(Tok::Word(w)) = p.peek() {
+                if w.eq_ignore_ascii_case("score") || w.eq_ignore_ascii_case("of") {
+                    break;
+                }
+                name.push(w.clone());
+                p.pos += 1;
+            }
+            if name.is_empty() {
+                return None;
+            }
+            p.eat_word("score");
+            if !p.eat(&Tok::AtLeast) {
+                p.eat_any_word(&["of", "at"])?;
+                p.eat_word("least");
+            }
+            let min = p.eat_num()?;
+            p.eat_or_better();
+            Some((name.join(" "), min))
+        })
+    }
+}
@@ -1,7 +1,8 @@
 use savefile_derive::Savefile;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-use crate::schedule::{CourseCode, Schedule};
+use crate::schedule::{CourseCode, Schedule, Term, TranscriptEntry};
 
 #[derive(Savefile, Serialize, Deserialize, Debug, Default, Hash, Clone, PartialEq, Eq)]
 pub enum CourseReq {
@@ -12,10 +13,90 @@ pub enum CourseReq {
     PreCourseGrade(CourseCode, Grade),
     CoCourseGrade(CourseCode, Grade),
     Program(String), // Assoc'd STEM
-    // Standing(u8), // May be Sen, Ju/Sen, Ju+, or So/Fr only -- how represent? TODO
     Instructor,
     #[default]
     NotRequired,
+    // Savefile stores variants by position, so new ones go last to keep older workbooks loading
+    /// Taken in the very same term, like a lecture and its lab. A course already taken before the
+    /// first semester counts too.
+    SameTermCourse(CourseCode),
+    Standing(Standing),
+    /// At least this many credits earned before the term
+    CreditsEarned(u32),
+    /// A minimum score on a named test, e.g. ACT Math or a placement exam
+    TestScore(String, u32),
+}
+
+/// Class standing, measured in credits earned before the term. Either bound may be left open, e.g.
+/// Junior standing is at least 60 credits, and "Fr/So only" at most 59.
+#[derive(Savefile, Serialize, Deserialize, Debug, Hash, Clone, Copy, PartialEq, Eq)]
+pub struct Standing {
+    pub min_credits: Option<u32>,
+    pub max_credits: Option<u32>,
+}
+
+impl Standing {
+    pub const SOPHOMORE_CREDITS: u32 = 30;
+    pub const JUNIOR_CREDITS: u32 = 60;
+    pub const SENIOR_CREDITS: u32 = 90;
+
+    pub fn at_least(credits: u32) -> Self {
+        Standing {
+            min_credits: Some(credits),
+            max_credits: None,
+        }
+    }
+
+    pub fn at_most(credits: u32) -> Self {
+        Standing {
+            min_credits: None,
+            max_credits: Some(credits),
+        }
+    }
+
+    pub fn between(min: u32, max: u32) -> Self {
+        Standing {
+            min_credits: Some(min),
+            max_credits: Some(max),
+        }
+    }
+
+    pub fn sophomore() -> Self {
+        Self::at_least(Self::SOPHOMORE_CREDITS)
+    }
+
+    pub fn junior() -> Self {
+        Self::at_least(Self::JUNIOR_CREDITS)
+    }
+
+    pub fn senior() -> Self {
+        Self::at_least(Self::SENIOR_CREDITS)
+    }
+
+    /// Freshmen and sophomores only
+    pub fn underclass() -> Self {
+        Self::at_most(Self::JUNIOR_CREDITS - 1)
+    }
+
+    pub fn admits(&self, credits_earned: u32) -> bool {
+        self.min_credits.is_none_or(|min| credits_earned >= min)
+            && self.max_credits.is_none_or(|max| credits_earned <= max)
+    }
+}
+
+impl fmt::Display for Standing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min_credits, self.max_credits) {
+            (Some(Self::SOPHOMORE_CREDITS), None) => write!(f, "Sophomore standing"),
+            (Some(Self::JUNIOR_CREDITS), None) => write!(f, "Junior standing"),
+            (Some(Self::SENIOR_CREDITS), None) => write!(f, "Senior standing"),
+            (None, Some(max)) if max == Self::JUNIOR_CREDITS - 1 => write!(f, "Fr/So standing"),
+            (Some(min), None) => write!(f, "at least {min} credits earned"),
+            (None, Some(max)) => write!(f, "at most {max} credits earned"),
+            (Some(min), Some(max)) => write!(f, "{min} to {max} credits earned"),
+            (None, None) => write!(f, "any standing"),
+        }
+    }
 }
 
 #[derive(Savefile, Serialize, Deserialize, Debug, Hash, Clone, Copy, PartialEq, Eq)]
@@ -60,8 +141,8 @@ impl Ord for GradeQualifier {
             (Plus, Plus) | (Minus, Minus) | (None, None) => std::cmp::Ordering::Equal,
             (Plus, _) => std::cmp::Ordering::Greater,
             (_, Plus) => std::cmp::Ordering::Less,
-            (Minus, _) => std::cmp::Ordering::Greater,
-            (_, Minus) => std::cmp::Ordering::Less,
+            (Minus, _) => std::cmp::Ordering::Less,
+            (_, Minus) => std::cmp::Ordering::Greater,
         }
     }
 }
@@ -86,6 +167,17 @@ impl Ord for Grade {
     }
 }
 
+impl fmt::Display for Grade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.letter)?;
+        match self.qualifier {
+            GradeQualifier::Plus => write!(f, "+"),
+            GradeQualifier::Minus => write!(f, "-"),
+            GradeQualifier::None => Ok(()),
+        }
+    }
+}
+
 #[macro_export]
 macro_rules! GR {
     ($l:ident +) => {
@@ -108,6 +200,53 @@ macro_rules! GR {
     };
 }
 
+impl fmt::Display for CourseReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let join = |f: &mut fmt::Formatter<'_>, reqs: &[CourseReq], sep: &str| {
+            write!(f, "(")?;
+            for (i, req) in reqs.iter().enumerate() {
+                if i > 0 {
+                    write!(f, " {sep} ")?;
+                }
+                write!(f, "{req}")?;
+            }
+            write!(f, ")")
+        };
+        match self {
+            CourseReq::And(reqs) => join(f, reqs, "and"),
+            CourseReq::Or(reqs) => join(f, reqs, "or"),
+            CourseReq::PreCourse(code) => write!(f, "{code}"),
+            CourseReq::CoCourse(code) => write!(f, "{code} (or concurrently)"),
+            CourseReq::SameTermCourse(code) => write!(f, "{code} (same term)"),
+            CourseReq::PreCourseGrade(code, grade) => write!(f, "{code} ({grade} or better)"),
+            CourseReq::CoCourseGrade(code, grade) => {
+                write!(f, "{code} ({grade} or better, or concurrently)")
+            }
+            CourseReq::Program(stem) => write!(f, "{stem} program"),
+            CourseReq::Standing(standing) => write!(f, "{standing}"),
+            CourseReq::CreditsEarned(credits) => write!(f, "completion of {credits} credit hours"),
+            CourseReq::TestScore(test, min) => write!(f, "{test} score of {min} or higher"),
+            CourseReq::Instructor => write!(f, "instructor consent"),
+            CourseReq::NotRequired => write!(f, "none"),
+        }
+    }
+}
+
+impl CourseReq {
+    /// The minimum grades this requirement asks for, as (course, grade) pairs.
+    pub fn grade_gates(&self) -> Vec<(&CourseCode, &Grade)> {
+        match self {
+            CourseReq::And(reqs) | CourseReq::Or(reqs) => {
+                reqs.iter().flat_map(CourseReq::grade_gates).collect()
+            }
+            CourseReq::PreCourseGrade(code, min) | CourseReq::CoCourseGrade(code, min) => {
+                vec![(code, min)]
+            }
+            _ => vec![],
+        }
+    }
+}
+
 // Used for script_assistant crate
 #[allow(dead_code)]
 impl CourseReq {
@@ -124,7 +263,9 @@ impl CourseReq {
                     req.collect_course_codes(codes);
                 }
             }
-            CourseReq::PreCourse(code) | CourseReq::CoCourse(code) => {
+            CourseReq::PreCourse(code)
+            | CourseReq::CoCourse(code)
+            | CourseReq::SameTermCourse(code) => {
                 codes.push(code);
             }
             CourseReq::PreCourseGrade(code, _) | CourseReq::CoCourseGrade(code, _) => {
@@ -135,25 +276,58 @@ impl CourseReq {
     }
 }
 
+/// Whether `code` was passed with at least `min` before `courses[upto]`, and whether that is only
+/// assumed because the course has not been graded yet (it is in progress, still planned, or retaken
+/// after a grade below `min`).
+fn grade_check(sched: &Schedule, code: &CourseCode, min: &Grade, upto: usize) -> (bool, bool) {
+    match sched.transcript.entry(code) {
+        Some(TranscriptEntry {
+            grade: Some(grade), ..
+        }) if grade < min => {
+            let retaken = sched.courses.iter().take(upto).flatten().any(|c| c == code);
+            (retaken, retaken)
+        }
+        Some(TranscriptEntry { grade: Some(_), .. }) => (true, false),
+        Some(_) => (true, true),
+        None => (true, !sched.incoming.contains(code)),
+    }
+}
+
 impl CourseReq {
-    pub fn is_satisfied(&self, sched: &Schedule, sem_idx: usize) -> bool {
-        // TODO: grade is not implemented
+    /// Whether this requirement of `course`, placed in `sched.courses[sem_idx]`, is met.
+    pub fn is_satisfied(&self, sched: &Schedule, course: &CourseCode, sem_idx: usize) -> bool {
+        let taken = match self {
+            CourseReq::PreCourse(code) | CourseReq::PreCourseGrade(code, _) => Some((code, 1)),
+            CourseReq::CoCourse(code) | CourseReq::CoCourseGrade(code, _) => Some((code, 2)),
+            _ => None,
+        }
+        .map(|(code, offset)| {
+            std::iter::once(&sched.incoming)
+                .chain(sched.courses.iter())
+                .take(sem_idx + offset)
+                .flatten()
+                .any(|c| c == code)
+        });
         match self {
-            CourseReq::And(reqs) => reqs.iter().all(|req| req.is_satisfied(sched, sem_idx)),
-            CourseReq::Or(reqs) => reqs.iter().any(|req| req.is_satisfied(sched, sem_idx)),
-            CourseReq::PreCourse(code) | CourseReq::PreCourseGrade(code, _) => {
-                std::iter::once(&sched.incoming)
-                    .chain(sched.courses.iter())
-                    .take(sem_idx + 1)
-                    .flatten()
-                    .any(|c| c == code)
+            CourseReq::And(reqs) => reqs
+                .iter()
+                .all(|req| req.is_satisfied(sched, course, sem_idx)),
+            CourseReq::Or(reqs) => reqs
+                .iter()
+                .any(|req| req.is_satisfied(sched, course, sem_idx)),
+            CourseReq::PreCourse(_) | CourseReq::CoCourse(_) => taken == Some(true),
+            CourseReq::SameTermCourse(code) => {
+                sched.incoming.contains(code)
+                    || sched
+                        .courses
+                        .get(sem_idx)
+                        .is_some_and(|sem| sem.contains(code))
+            }
+            CourseReq::PreCourseGrade(code, min) => {
+                taken == Some(true) && grade_check(sched, code, min, sem_idx).0
             }
-            CourseReq::CoCourse(code) | CourseReq::CoCourseGrade(code, _) => {
-                std::iter::once(&sched.incoming)
-                    .chain(sched.courses.iter())
-                    .take(sem_idx + 2)
-                    .flatten()
-                    .any(|c| c == code)
+            CourseReq::CoCourseGrade(code, min) => {
+                taken == Some(true) && grade_check(sched, code, min, sem_idx + 1).0
             }
             CourseReq::Program(x) => sched.programs.iter().any(|p| {
                 sched
@@ -162,8 +336,354 @@ impl CourseReq {
                     .iter()
                     .any(|y| y.name == *p && y.assoc_stems.contains(x))
             }),
-            CourseReq::Instructor => todo!(),
+            CourseReq::Standing(standing) => standing.admits(sched.credits_before(sem_idx)),
+            CourseReq::CreditsEarned(credits) => sched.credits_before(sem_idx) >= *credits,
+            CourseReq::TestScore(test, min) => sched.test_score(test).is_some_and(|s| s >= *min),
+            CourseReq::Instructor => sched.has_consent(course),
             CourseReq::NotRequired => true,
         }
     }
 }
+
+/// A course a prerequisite needed but did not find in time.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MissingCourse {
+    pub code: CourseCode,
+    /// The last semester (index into `Schedule::courses`) the course could have been taken in, or
+    /// `None` if it would have had to be incoming credit.
+    pub semester: Option<usize>,
+    pub term: Option<Term>,
+    /// The course had to be taken in exactly that semester, not by then
+    #[serde(default)]
+    pub same_term: bool,
+}
+
+impl fmt::Display for MissingCourse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.term {
+            Some(term) if self.same_term => write!(f, "{} in {term}", self.code),
+            Some(term) => write!(f, "{} by {term}", self.code),
+            None => write!(f, "{} as incoming credit", self.code),
+        }
+    }
+}
+
+/// The result of evaluating a [`CourseReq`] tree against a schedule, node by node.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReqExplanation {
+    /// The requirement evaluated at this node; `And`/`Or` nodes hold their whole subtree.
+    pub req: CourseReq,
+    pub satisfied: bool,
+    /// Satisfied only on the assumption that a course not graded yet will earn the minimum grade
+    pub provisional: bool,
+    /// Satisfied only through the instructor consent recorded for the course
+    pub by_consent: bool,
+    /// Set on unsatisfied course nodes
+    pub missing: Option<MissingCourse>,
+    pub children: Vec<ReqExplanation>,
+}
+
+impl ReqExplanation {
+    /// Every missing course below an unsatisfied node, in tree order.
+    pub fn missing_courses(&self) -> Vec<&MissingCourse> {
+        let mut missing = Vec::new();
+        self.collect_missing(&mut missing);
+        missing
+    }
+
+    fn collect_missing<'a>(&'a self, missing: &mut Vec<&'a MissingCourse>) {
+        if self.satisfied {
+            return;
+        }
+        missing.extend(&self.missing);
+        for child in &self.children {
+            child.collect_missing(missing);
+        }
+    }
+
+    /// Unsatisfied leaves, i.e. the individual reasons the requirement fails.
+    pub fn unmet_leaves(&self) -> Vec<&ReqExplanation> {
+        if self.satisfied {
+            return vec![];
+        }
+        if self.children.is_empty() {
+            return vec![self];
+        }
+        self.children
+            .iter()
+            .flat_map(|c| c.unmet_leaves())
+            .collect()
+    }
+
+    /// Grade-gated leaves the requirement relies on before their courses are graded.
+    pub fn provisional_leaves(&self) -> Vec<&ReqExplanation> {
+        self.flagged_leaves(|e| e.provisional)
+    }
+
+    fn flagged_leaves(&self, flag: fn(&ReqExplanation) -> bool) -> Vec<&ReqExplanation> {
+        if !flag(self) {
+            return vec![];
+        }
+        if self.children.is_empty() {
+            return vec![self];
+        }
+        self.children
+            .iter()
+            .flat_map(|c| c.flagged_leaves(flag))
+            .collect()
+    }
+
+    /// Courses from satisfied course nodes, i.e. the courses counted towards the requirement.
+    pub fn applied_courses(&self) -> Vec<&CourseCode> {
+        match &self.req {
+            CourseReq::And(_) | CourseReq::Or(_) => self
+                .children
+                .iter()
+                .flat_map(|c| c.applied_courses())
+                .collect(),
+            CourseReq::PreCourse(code)
+            | CourseReq::CoCourse(code)
+            | CourseReq::PreCourseGrade(code, _)
+            | CourseReq::CoCourseGrade(code, _)
+            | CourseReq::SameTermCourse(code)
+                if self.satisfied =>
+            {
+                vec![code]
+            }
+            _ => vec![],
+        }
+    }
+
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let status = match (self.satisfied, self.provisional, self.by_consent) {
+            (true, false, false) => "Met",
+            (true, true, _) => "Provisional",
+            (true, false, true) => "Met by consent",
+            (false, _, _) => "Unmet",
+        };
+        write!(f, "{:indent$}[{status}] ", "", indent = 2 * depth)?;
+        match &self.req {
+            CourseReq::And(_) => writeln!(f, "all of:")?,
+            CourseReq::Or(_) => writeln!(f, "one of:")?,
+            req => match &self.missing {
+                Some(missing) => writeln!(f, "{req}: needs {missing}")?,
+                None => writeln!(f, "{req}")?,
+            },
+        }
+        for child in &self.children {
+            child.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ReqExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_indented(f, 0)
+    }
+}
+
+impl CourseReq {
+    /// Evaluate the requirement like [`CourseReq::is_satisfied`], but keep the result of every node and,
+    /// for each missing course, the semester it was needed by.
+    pub fn explain(&self, sched: &Schedule, course: &CourseCode, sem_idx: usize) -> ReqExplanation {
+        let children: Vec<ReqExplanation> = match self {
+            CourseReq::And(reqs) | CourseReq::Or(reqs) => reqs
+                .iter()
+                .map(|req| req.explain(sched, course, sem_idx))
+                .collect(),
+            _ => vec![],
+        };
+        let satisfied = match self {
+            CourseReq::And(_) => children.iter().all(|c| c.satisfied),
+            CourseReq::Or(_) => children.iter().any(|c| c.satisfied),
+            req => req.is_satisfied(sched, course, sem_idx),
+        };
+        // Whether the node is met only thanks to leaves with `flag` set, `leaf` being the flag of this node
+        // if it is a leaf
+        let relies_on = |flag: fn(&ReqExplanation) -> bool, leaf: bool| {
+            satisfied
+                && match self {
+                    CourseReq::And(_) => children.iter().any(flag),
+                    CourseReq::Or(_) => !children.iter().any(|c| c.satisfied && !flag(c)),
+                    _ => leaf,
+                }
+        };
+        let provisional = relies_on(
+            |c| c.provisional,
+            match self {
+                CourseReq::PreCourseGrade(code, min) => grade_check(sched, code, min, sem_idx).1,
+                CourseReq::CoCourseGrade(code, min) => grade_check(sched, code, min, sem_idx + 1).1,
+                _ => false,
+            },
+        );
+        let by_consent = relies_on(|c| c.by_consent, *self == CourseReq::Instructor);
+        // Prior courses must be done a semester earlier, concurrent ones may share the semester
+        let latest = match self {
+            CourseReq::PreCourse(code) | CourseReq::PreCourseGrade(code, _) => {
+                Some((code, sem_idx.checked_sub(1)))
+            }
+            CourseReq::CoCourse(code)
+            | CourseReq::CoCourseGrade(code, _)
+            | CourseReq::SameTermCourse(code) => Some((code, Some(sem_idx))),
+            _ => None,
+        };
+        let missing = latest
+            .filter(|_| !satisfied)
+            .map(|(code, semester)| MissingCourse {
+                code: code.clone(),
+                semester,
+                term: semester.map(|s| sched.term(s)),
+                same_term: matches!(self, CourseReq::SameTermCourse(_)),
+            });
+        ReqExplanation {
+            req: self.clone(),
+            satisfied,
+            provisional,
+            by_consent,
+            missing,
+            children,
+        }
+    }
+}
+
+/// The credits-earned range a credit-based requirement admits, as (minimum, maximum).
+fn credit_range(req: &CourseReq) -> Option<(u32, Option<u32>)> {
+    match req {
+        CourseReq::Standing(standing) => {
+            Some((standing.min_credits.unwrap_or(0), standing.max_credits))
+        }
+        CourseReq::CreditsEarned(credits) => Some((*credits, None)),
+        _ => None,
+    }
+}
+
+/// Whether the leaf `a` being met means `b` is met too.
+fn leaf_implies(a: &CourseReq, b: &CourseReq) -> bool {
+    use CourseReq::*;
+    if a == b || *b == NotRequired {
+        return true;
+    }
+    if let (Some((a_min, a_max)), Some((b_min, b_max))) = (credit_range(a), credit_range(b)) {
+        return a_min >= b_min
+            && b_max.is_none_or(|b_max| a_max.is_some_and(|a_max| a_max <= b_max));
+    }
+    match (a, b) {
+        // Taking a course earlier satisfies "or concurrently", and a minimum grade implies passing
+        (PreCourse(x), CoCourse(y))
+        | (PreCourseGrade(x, _), PreCourse(y) | CoCourse(y))
+        | (CoCourseGrade(x, _), CoCourse(y))
+        | (SameTermCourse(x), CoCourse(y)) => x == y,
+        (PreCourseGrade(x, g), PreCourseGrade(y, h) | CoCourseGrade(y, h))
+        | (CoCourseGrade(x, g), CoCourseGrade(y, h)) => x == y && g >= h,
+        (TestScore(x, m), TestScore(y, n)) => x == y && m >= n,
+        _ => false,
+    }
+}
+
+// The parts of `req` that must all hold, with nested `And`s flattened
+fn conjuncts(req: &CourseReq) -> Vec<&CourseReq> {
+    match req {
+        CourseReq::And(reqs) => reqs.iter().flat_map(conjuncts).collect(),
+        req => vec![req],
+    }
+}
+
+// The parts of `req` one of which must hold, with nested `Or`s flattened
+fn disjuncts(req: &CourseReq) -> Vec<&CourseReq> {
+    match req {
+        CourseReq::Or(reqs) => reqs.iter().flat_map(disjuncts).collect(),
+        req => vec![req],
+    }
+}
+
+/// Whether every schedule meeting `a` also meets `b`. Alternatives are distributed out until both
+/// sides are plain lists of leaves, which can take time exponential in the number of `Or`s.
+fn implies(a: &CourseReq, b: &CourseReq) -> bool {
+    if let CourseReq::Or(reqs) = a {
+        return reqs.iter().all(|x| implies(x, b));
+    }
+    if let CourseReq::And(reqs) = b {
+        return reqs.iter().all(|y| implies(a, y));
+    }
+
+    let all = conjuncts(a);
+    if let Some(pos) = all.iter().position(|x| matches!(x, CourseReq::Or(_))) {
+        // (X and (Y or Z)) implies b iff (X and Y) and (X and Z) both do
+        return disjuncts(all[pos]).into_iter().all(|alt| {
+            let mut parts: Vec<CourseReq> = all.iter().map(|&x| x.clone()).collect();
+            parts[pos] = alt.clone();
+            implies(&CourseReq::And(parts), b)
+        });
+    }
+    let any = disjuncts(b);
+    if let Some(pos) = any.iter().position(|y| matches!(y, CourseReq::And(_))) {
+        // a implies (X or (Y and Z)) iff it implies both (X or Y) and (X or Z)
+        return conjuncts(any[pos]).into_iter().all(|part| {
+            let mut alts: Vec<CourseReq> = any.iter().map(|&y| y.clone()).collect();
+            alts[pos] = part.clone();
+            implies(a, &CourseReq::Or(alts))
+        });
+    }
+    any.iter()
+        .any(|y| **y == CourseReq::NotRequired || all.iter().any(|x| leaf_implies(x, y)))
+}
+
+impl CourseReq {
+    /// An equivalent requirement without redundant structure: nested `And`s and `Or`s are flattened,
+    /// duplicates and parts implied by others are dropped, and single-child `And`s and `Or`s are
+    /// replaced by their child. The order of what remains is kept.
+    pub fn normalize(&self) -> CourseReq {
+        let (reqs, any) = match self {
+            CourseReq::And(reqs) => (reqs, false),
+            CourseReq::Or(reqs) => (reqs, true),
+            req => return req.clone(),
+        };
+        let mut parts: Vec<CourseReq> = Vec::new();
+        for req in reqs.iter().map(CourseReq::normalize) {
+            let flat = match req {
+                CourseReq::Or(inner) if any => inner,
+                CourseReq::And(inner) if !any => inner,
+                req => vec![req],
+            };
+            for req in flat {
+                if !parts.contains(&req) {
+                    parts.push(req);
+                }
+            }
+        }
+
+        // In an `And`, a part implied by another is redundant; in an `Or`, a part implying another is.
+        // Of two equivalent parts the first is kept.
+        let redundant = |i: usize, j: usize| {
+            let (weaker, stronger) = if any { (j, i) } else { (i, j) };
+            implies(&parts[stronger], &parts[weaker])
+                && (j < i || !implies(&parts[weaker], &parts[stronger]))
+        };
+        let mut keep = vec![true; parts.len()];
+        for i in 0..parts.len() {
+            keep[i] = !(0..parts.len()).any(|j| j != i && keep[j] && redundant(i, j));
+        }
+        let mut parts: Vec<CourseReq> = parts
+            .into_iter()
+            .zip(keep)
+            .filter_map(|(part, keep)| keep.then_some(part))
+            .collect();
+
+        if any && parts.contains(&CourseReq::NotRequired) {
+            return CourseReq::NotRequired;
+        }
+        parts.retain(|part| *part != CourseReq::NotRequired);
+        match parts.len() {
+            0 if !any => CourseReq::NotRequired,
+            1 => parts.remove(0),
+            _ if any => CourseReq::Or(parts),
+            _ => CourseReq::And(parts),
+        }
+    }
+
+    /// Whether both requirements are met by exactly the same schedules, however they are structured.
+    pub fn is_equivalent(&self, other: &CourseReq) -> bool {
+        self == other || (implies(self, other) && implies(other, self))
+    }
+}
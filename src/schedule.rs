@@ -1,13 +1,14 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 use savefile_derive::Savefile;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     fmt::{self, Display},
+    str::FromStr,
 };
 
-use crate::geneds::{ElectiveReq, GenEd, are_geneds_satisfied};
-use crate::prereqs::CourseReq;
+use crate::geneds::{ElectiveReq, GenEd, satisfy_req};
+use crate::prereqs::{CourseReq, Grade};
 
 #[derive(Savefile, Serialize, Deserialize, Debug, Clone, Hash, Eq, PartialEq)]
 pub enum CourseTermOffering {
@@ -17,6 +18,99 @@ pub enum CourseTermOffering {
     Discretion,
     Infrequently,
     Summer,
+    /// Fall semesters of odd calendar years only (Fall 2025, Fall 2027, ...)
+    FallOdd,
+    FallEven,
+    /// Spring semesters of odd calendar years only (Spring 2027, Spring 2029, ...)
+    SpringOdd,
+    SpringEven,
+    /// Exactly the listed terms
+    Terms(Vec<Term>),
+}
+
+impl CourseTermOffering {
+    /// Whether the course is taught in `term`. Courses offered at the department's discretion or
+    /// infrequently are assumed to be available in any Fall or Spring semester.
+    pub fn is_offered(&self, term: &Term) -> bool {
+        use CourseTermOffering::*;
+        let odd_year = term.year % 2 == 1;
+        match self {
+            Fall => term.season == Season::Fall,
+            Spring => term.season == Season::Spring,
+            Both | Discretion | Infrequently => term.season != Season::Summer,
+            Summer => term.season == Season::Summer,
+            FallOdd => term.season == Season::Fall && odd_year,
+            FallEven => term.season == Season::Fall && !odd_year,
+            SpringOdd => term.season == Season::Spring && odd_year,
+            SpringEven => term.season == Season::Spring && !odd_year,
+            Terms(terms) => terms.contains(term),
+        }
+    }
+}
+
+#[derive(Savefile, Serialize, Deserialize, Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum Season {
+    Spring,
+    Summer,
+    Fall,
+}
+
+/// A specific academic term, e.g. Fall 2025. `year` is the calendar year the term takes place in.
+#[derive(Savefile, Serialize, Deserialize, Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub struct Term {
+    pub season: Season,
+    pub year: u32,
+}
+
+impl Term {
+    pub fn new(season: Season, year: u32) -> Self {
+        Term { season, year }
+    }
+
+    /// The `low_year` of the academic year (and catalog) this term belongs to: Fall 2025, Spring 2026
+    /// and Summer 2026 all belong to 2025-2026.
+    pub fn academic_year(&self) -> u32 {
+        match self.season {
+            Season::Fall => self.year,
+            Season::Spring | Season::Summer => self.year - 1,
+        }
+    }
+
+    /// The Fall or Spring semester following this term.
+    pub fn next_semester(&self) -> Term {
+        match self.season {
+            Season::Fall => Term::new(Season::Spring, self.year + 1),
+            Season::Spring | Season::Summer => Term::new(Season::Fall, self.year),
+        }
+    }
+}
+
+impl Display for Season {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Season::Spring => write!(f, "Spring"),
+            Season::Summer => write!(f, "Summer"),
+            Season::Fall => write!(f, "Fall"),
+        }
+    }
+}
+
+impl Display for Term {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.season, self.year)
+    }
+}
+
+impl PartialOrd for Term {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Term {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.year, self.season as u8).cmp(&(other.year, other.season as u8))
+    }
 }
 
 #[derive(Savefile, Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
@@ -67,12 +161,56 @@ impl Display for CourseCodeSuffix {
     }
 }
 
+// Serialized as "STEM-CODE" so that catalog data files can use course codes as map keys. Unique
+// suffixes are written "STEM-#ID" to keep them apart from course numbers.
 #[derive(Savefile, Serialize, Deserialize, Clone, Hash, PartialEq, Eq)]
+#[serde(into = "String", try_from = "String")]
 pub struct CourseCode {
     pub stem: String,
     pub code: CourseCodeSuffix,
 }
 
+impl FromStr for CourseCode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (stem, code) = s
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| anyhow::anyhow!("Invalid course code format: {s}"))?;
+        if stem.is_empty() || code.is_empty() {
+            bail!("Invalid course code format: {s}");
+        }
+        Ok(CourseCode {
+            stem: stem.to_ascii_uppercase(),
+            code: match code.parse::<usize>() {
+                Ok(num) => num.into(),
+                Err(_) => match code.strip_prefix('#').and_then(|id| id.parse().ok()) {
+                    Some(id) => CourseCodeSuffix::Unique(id),
+                    None => code.into(),
+                },
+            },
+        })
+    }
+}
+
+impl TryFrom<String> for CourseCode {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<CourseCode> for String {
+    fn from(code: CourseCode) -> Self {
+        match code.code {
+            CourseCodeSuffix::Unique(id) => format!("{}-#{id}", code.stem),
+            _ => code.to_string(),
+        }
+    }
+}
+
 #[macro_export]
 // #[macro_use]
 macro_rules! CC {
@@ -96,14 +234,173 @@ impl std::fmt::Debug for CourseCode {
     }
 }
 
+impl CourseCode {
+    /// The course level implied by the course number, e.g. 3000 for CHEM-3301.
+    pub fn level(&self) -> Option<u32> {
+        match self.code {
+            CourseCodeSuffix::Number(num) => Some((num / 1000 * 1000) as u32),
+            CourseCodeSuffix::Special(_) | CourseCodeSuffix::Unique(_) => None,
+        }
+    }
+}
+
 pub type Semester = Vec<CourseCode>;
 
+/// Credit hours for a course. Variable-credit courses (research, special topics, ...) have `min < max`.
+#[derive(Savefile, Serialize, Deserialize, Debug, Clone, Copy, Hash, PartialEq, Eq)]
+#[serde(from = "CreditsRepr", into = "CreditsRepr")]
+pub struct Credits {
+    pub min: u32,
+    pub max: u32,
+}
+
+// Lets data files write `credits = 3` for fixed-credit courses and `{ min = 1, max = 3 }` otherwise
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum CreditsRepr {
+    Fixed(u32),
+    Range { min: u32, max: u32 },
+}
+
+impl From<CreditsRepr> for Credits {
+    fn from(repr: CreditsRepr) -> Self {
+        match repr {
+            CreditsRepr::Fixed(num) => Credits::fixed(num),
+            CreditsRepr::Range { min, max } => Credits::range(min, max),
+        }
+    }
+}
+
+impl From<Credits> for CreditsRepr {
+    fn from(credits: Credits) -> Self {
+        if credits.is_variable() {
+            CreditsRepr::Range {
+                min: credits.min,
+                max: credits.max,
+            }
+        } else {
+            CreditsRepr::Fixed(credits.min)
+        }
+    }
+}
+
+impl Credits {
+    pub fn fixed(num: u32) -> Self {
+        Credits { min: num, max: num }
+    }
+
+    pub fn range(min: u32, max: u32) -> Self {
+        Credits {
+            min: min.min(max),
+            max: max.max(min),
+        }
+    }
+
+    pub fn is_variable(&self) -> bool {
+        self.min != self.max
+    }
+}
+
+impl From<u32> for Credits {
+    fn from(num: u32) -> Self {
+        Credits::fixed(num)
+    }
+}
+
+impl Display for Credits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_variable() {
+            write!(f, "{}-{}", self.min, self.max)
+        } else {
+            write!(f, "{}", self.min)
+        }
+    }
+}
+
+fn is_false(b: &bool) -> bool {
+    !b
+}
+
+#[derive(Savefile, Serialize, Deserialize, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct CourseInfo {
+    pub title: String,
+    /// `None` when the catalog lists no credit value; such courses count as 0 credits.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credits: Option<Credits>,
+    pub offering: CourseTermOffering,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<u32>,
+    /// Lab section rather than lecture.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub lab: bool,
+    /// May be taken more than once for credit.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub repeatable: bool,
+    /// The same course under other codes; it is taken and counted at most once between them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cross_listed: Vec<CourseCode>,
+}
+
+impl CourseInfo {
+    pub fn new(
+        title: impl Into<String>,
+        credits: Option<u32>,
+        offering: CourseTermOffering,
+    ) -> Self {
+        CourseInfo {
+            title: title.into(),
+            credits: credits.map(Credits::fixed),
+            offering,
+            level: None,
+            lab: false,
+            repeatable: false,
+            cross_listed: Vec::new(),
+        }
+    }
+
+    /// Build the record for a plain catalog listing, inferring the level from the course number and
+    /// the lab flag from a title such as "General Chemistry I Lab".
+    pub fn from_listing(
+        code: &CourseCode,
+        title: impl Into<String>,
+        credits: Option<u32>,
+        offering: CourseTermOffering,
+    ) -> Self {
+        let mut info = CourseInfo::new(title, credits, offering);
+        info.level = code.level();
+        info.lab = info.title.split_whitespace().any(|word| word == "Lab");
+        info
+    }
+
+    /// Credits counted for this course when no particular value has been chosen.
+    pub fn min_credits(&self) -> u32 {
+        self.credits.map_or(0, |c| c.min)
+    }
+
+    pub fn max_credits(&self) -> u32 {
+        self.credits.map_or(0, |c| c.max)
+    }
+}
+
 #[derive(Savefile, Serialize, Deserialize, Debug, Clone)]
 pub struct Elective {
     pub name: String,
     pub req: ElectiveReq,
 }
 
+/// An elective group of a scheduled program that the schedule does not satisfy.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct UnmetElective {
+    pub program: String,
+    pub elective: String,
+}
+
+impl fmt::Display for UnmetElective {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.program, self.elective)
+    }
+}
+
 #[derive(Savefile, Serialize, Deserialize, Debug, Clone)]
 pub struct Program {
     pub name: String,
@@ -117,10 +414,85 @@ pub struct Catalog {
     pub programs: Vec<Program>,
     pub geneds: Vec<GenEd>,
     pub prereqs: HashMap<CourseCode, CourseReq>,
-    pub courses: HashMap<CourseCode, (String, Option<u32>, CourseTermOffering)>,
+    #[serde(deserialize_with = "deserialize_courses")]
+    #[savefile_versions_as = "0..1999:LegacyCourses"]
+    pub courses: HashMap<CourseCode, CourseInfo>,
     pub low_year: u32,
 }
 
+/// A catalog listing as saved before [`CourseInfo`]: title, credits and term offering.
+type Listing = (String, Option<u32>, CourseTermOffering);
+
+/// The course table of catalogs saved by 0.1.x.
+#[derive(Savefile)]
+pub(crate) struct LegacyCourses(HashMap<CourseCode, Listing>);
+
+impl From<LegacyCourses> for HashMap<CourseCode, CourseInfo> {
+    fn from(LegacyCourses(listings): LegacyCourses) -> Self {
+        listings
+            .into_iter()
+            .map(|(code, (title, credits, offering))| {
+                let info = CourseInfo::from_listing(&code, title, credits, offering);
+                (code, info)
+            })
+            .collect()
+    }
+}
+
+// Accept both course records and the listings older JSON plans hold
+fn deserialize_courses<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<HashMap<CourseCode, CourseInfo>, D::Error> {
+    // Listings first: a struct also deserializes from a sequence, which would skip from_listing
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Entry {
+        Listing(Listing),
+        Info(CourseInfo),
+    }
+    let entries = HashMap::<CourseCode, Entry>::deserialize(deserializer)?;
+    Ok(entries
+        .into_iter()
+        .map(|(code, entry)| {
+            let info = match entry {
+                Entry::Info(info) => info,
+                Entry::Listing((title, credits, offering)) => {
+                    CourseInfo::from_listing(&code, title, credits, offering)
+                }
+            };
+            (code, info)
+        })
+        .collect())
+}
+
+impl Catalog {
+    /// An empty catalog whose first academic year starts in the Fall of `low_year`.
+    pub fn new(low_year: u32) -> Self {
+        Catalog {
+            programs: Vec::new(),
+            geneds: Vec::new(),
+            prereqs: HashMap::new(),
+            courses: HashMap::new(),
+            low_year,
+        }
+    }
+
+    /// Whether `a` and `b` are the same course, possibly under cross-listed codes.
+    pub fn is_same_course(&self, a: &CourseCode, b: &CourseCode) -> bool {
+        let lists = |x: &CourseCode, y: &CourseCode| {
+            self.courses
+                .get(x)
+                .is_some_and(|info| info.cross_listed.contains(y))
+        };
+        a == b || lists(a, b) || lists(b, a)
+    }
+
+    /// Whether taking `code` again (or a cross-listing of it) earns credit again.
+    pub fn is_repeatable(&self, code: &CourseCode) -> bool {
+        self.courses.get(code).is_some_and(|info| info.repeatable)
+    }
+}
+
 impl PartialEq for Catalog {
     fn eq(&self, other: &Self) -> bool {
         self.low_year == other.low_year // Assumes that no two Catalogs will have the same low_year
@@ -129,6 +501,24 @@ impl PartialEq for Catalog {
 
 impl Eq for Catalog {}
 
+impl std::hash::Hash for Catalog {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.low_year.hash(state);
+    }
+}
+
+impl PartialOrd for Catalog {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Catalog {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.low_year.cmp(&other.low_year)
+    }
+}
+
 impl fmt::Display for Catalog {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.write_fmt(format_args!(
@@ -139,14 +529,92 @@ impl fmt::Display for Catalog {
     }
 }
 
+/// Whether, and how, summer terms are planned between the Spring and Fall semesters.
+#[derive(Savefile, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SummerOptions {
+    pub enabled: bool,
+    /// Credit cap for each summer term
+    pub max_credits: u32,
+    /// Penalty for each summer term the solver uses, counted like credits of uneven semester loads
+    pub cost: u32,
+}
+
+impl Default for SummerOptions {
+    fn default() -> Self {
+        SummerOptions {
+            enabled: false,
+            max_credits: 7,
+            cost: 3,
+        }
+    }
+}
+
+/// A course on the student's transcript. `grade` is `None` while the course is in progress.
+#[derive(Savefile, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TranscriptEntry {
+    pub code: CourseCode,
+    pub grade: Option<Grade>,
+}
+
+/// Courses completed (or in progress) before the planned semesters, with the grades earned.
+#[derive(Savefile, Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transcript {
+    pub courses: Vec<TranscriptEntry>,
+}
+
+impl Transcript {
+    pub fn entry(&self, code: &CourseCode) -> Option<&TranscriptEntry> {
+        self.courses.iter().find(|e| e.code == *code)
+    }
+
+    /// The grade earned in `code`, if it has been graded.
+    pub fn grade(&self, code: &CourseCode) -> Option<&Grade> {
+        self.entry(code).and_then(|e| e.grade.as_ref())
+    }
+}
+
 #[derive(Savefile, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Schedule {
     pub courses: Vec<Semester>,
     pub programs: Vec<String>,
     pub incoming: Semester,
+    // Fields below up to `catalog` were added in 0.2.0 (savefile version 2000) and default when
+    // loading workbooks saved by 0.1.x
+    /// Grades for the incoming courses; set with [`Schedule::set_transcript`]. Incoming courses not on the
+    /// transcript (e.g. AP or transfer credit) count as passed without a grade.
+    #[serde(default)]
+    #[savefile_versions = "2000.."]
+    pub transcript: Transcript,
+    /// Courses the student has instructor or department consent to take; see [`Schedule::grant_consent`].
+    #[serde(default)]
+    #[savefile_versions = "2000.."]
+    pub consents: Vec<CourseCode>,
+    /// The student's scores on named tests (ACT Math, placement exams, ...), for `TestScore` prereqs
+    #[serde(default)]
+    #[savefile_versions = "2000.."]
+    pub test_scores: HashMap<String, u32>,
+    /// Credits taken for variable-credit courses. Courses without an entry count their minimum credits.
+    #[serde(default)]
+    #[savefile_versions = "2000.."]
+    pub credits: HashMap<CourseCode, u32>,
+    /// The term of `courses[0]`; later semesters alternate Fall/Spring from here, with a summer term
+    /// after each Spring if summers are enabled.
+    #[serde(default = "legacy_start")]
+    #[savefile_versions = "2000.."]
+    #[savefile_default_fn = "legacy_start"]
+    pub start: Term,
+    /// Change with [`Schedule::set_summer_options`], which keeps `courses` in step.
+    #[serde(default)]
+    #[savefile_versions = "2000.."]
+    pub summer: SummerOptions,
     pub catalog: Catalog,
 }
 
+// Schedules saved before they recorded a start term all followed the 2025-2026 catalog from its first Fall
+fn legacy_start() -> Term {
+    Term::new(Season::Fall, 2025)
+}
+
 pub fn generate_schedule(
     programs: Vec<&str>,
     catalog: Catalog,
@@ -171,10 +639,9 @@ pub fn generate_schedule(
     }
 
     let mut sched = Schedule {
-        courses: combined_semesters,
         programs: programs.iter().map(|x| x.name.to_owned()).collect(),
         incoming: incoming.unwrap_or_default(),
-        catalog,
+        ..Schedule::new(catalog, combined_semesters)
     };
     sched.reduce()?;
     println!("Is schedule valid? {}", sched.is_valid()?);
@@ -182,12 +649,33 @@ pub fn generate_schedule(
 }
 
 impl Schedule {
+    /// A plan of `courses` starting in the Fall of the catalog's first year, with no programs,
+    /// incoming credit, transcript, consents, test scores or credit choices, and no summer terms.
+    pub fn new(catalog: Catalog, courses: Vec<Semester>) -> Self {
+        Schedule {
+            courses,
+            programs: Vec::new(),
+            incoming: Vec::new(),
+            transcript: Transcript::default(),
+            consents: Vec::new(),
+            test_scores: HashMap::new(),
+            credits: HashMap::new(),
+            start: Term::new(Season::Fall, catalog.low_year),
+            summer: SummerOptions::default(),
+            catalog,
+        }
+    }
+
+    /// Drop courses planned more than once, including under a cross-listed code, unless the catalog
+    /// marks them repeatable.
     pub fn reduce(&mut self) -> Result<&mut Self> {
-        let mut all_codes: HashSet<CourseCode> = HashSet::new();
+        let mut all_codes: Vec<CourseCode> = Vec::new();
+        let catalog = &self.catalog;
         self.courses.iter_mut().for_each(|sem| {
             sem.retain(|code| {
-                if !all_codes.contains(code) {
-                    all_codes.insert(code.clone()); // TODO: is cloning necessary?
+                let repeat = all_codes.iter().any(|c| catalog.is_same_course(c, code));
+                if !repeat || catalog.is_repeatable(code) {
+                    all_codes.push(code.clone());
                     true
                 } else {
                     false
@@ -197,50 +685,159 @@ impl Schedule {
         Ok(self)
     }
 
+    /// Attach the student's transcript, adding its courses to the incoming courses.
+    pub fn set_transcript(&mut self, transcript: Transcript) {
+        for entry in &transcript.courses {
+            if !self.incoming.contains(&entry.code) {
+                self.incoming.push(entry.code.clone());
+            }
+        }
+        self.transcript = transcript;
+    }
+
+    /// Record instructor or department consent for `code`, which satisfies its
+    /// [`CourseReq::Instructor`] prerequisites.
+    pub fn grant_consent(&mut self, code: CourseCode) {
+        if !self.consents.contains(&code) {
+            self.consents.push(code);
+        }
+    }
+
+    pub fn revoke_consent(&mut self, code: &CourseCode) {
+        self.consents.retain(|c| c != code);
+    }
+
+    pub fn has_consent(&self, code: &CourseCode) -> bool {
+        self.consents.contains(code)
+    }
+
+    pub fn set_test_score(&mut self, test: impl Into<String>, score: u32) {
+        self.test_scores.insert(test.into(), score);
+    }
+
+    pub fn test_score(&self, test: &str) -> Option<u32> {
+        self.test_scores.get(test).copied()
+    }
+
+    /// Credits earned before `courses[sem_idx]`, including incoming credit. A course taken again, or
+    /// under a cross-listed code, only earns credit again if it is repeatable.
+    pub fn credits_before(&self, sem_idx: usize) -> u32 {
+        let mut counted: Vec<&CourseCode> = Vec::new();
+        let mut total = 0;
+        for code in std::iter::once(&self.incoming)
+            .chain(self.courses.iter().take(sem_idx))
+            .flatten()
+        {
+            let repeat = counted.iter().any(|c| self.catalog.is_same_course(c, code));
+            if repeat && !self.catalog.is_repeatable(code) {
+                continue;
+            }
+            counted.push(code);
+            total += self.course_credits(code);
+        }
+        total
+    }
+
+    /// Whether every requirement in [`Schedule::audit`] is met.
     pub fn is_valid(&self) -> Result<bool> {
-        Ok(dbg!(self.are_programs_valid()?)
-            && dbg!(self.validate_prereqs()?)
-            && dbg!(self.are_geneds_fulfilled()?))
+        Ok(self.audit()?.is_satisfied())
     }
 
-    pub fn validate(&mut self) -> Result<()> {
-        crate::model::two_stage_lex_schedule(self, crate::MAX_CREDITS_PER_SEMESTER)?;
+    /// The term `courses[sem_idx]` takes place in.
+    pub fn term(&self, sem_idx: usize) -> Term {
+        (0..sem_idx).fold(self.start, |term, _| match term.season {
+            Season::Spring if self.summer.enabled => Term::new(Season::Summer, term.year),
+            _ => term.next_semester(),
+        })
+    }
+
+    /// Change the summer options, adding an empty summer term between each Spring and Fall semester
+    /// when summers get enabled, and removing the summer terms when they get disabled. Fails if a
+    /// summer term to be removed still has courses in it.
+    pub fn set_summer_options(&mut self, summer: SummerOptions) -> Result<()> {
+        if summer.enabled == self.summer.enabled {
+            self.summer = summer;
+            return Ok(());
+        }
+        let is_summer: Vec<bool> = (0..self.courses.len())
+            .map(|sem_idx| self.term(sem_idx).season == Season::Summer)
+            .collect();
+        if let Some(sem_idx) = (0..self.courses.len())
+            .find(|&sem_idx| is_summer[sem_idx] && !self.courses[sem_idx].is_empty())
+        {
+            bail!(
+                "Cannot disable summer terms: {} has courses planned",
+                self.term(sem_idx)
+            );
+        }
+
+        let regular: Vec<Semester> = std::mem::take(&mut self.courses)
+            .into_iter()
+            .zip(is_summer)
+            .filter_map(|(sem, is_summer)| (!is_summer).then_some(sem))
+            .collect();
+        self.summer = summer;
+        for sem in regular {
+            if !self.courses.is_empty() && self.term(self.courses.len()).season == Season::Summer {
+                self.courses.push(Semester::new());
+            }
+            self.courses.push(sem);
+        }
         Ok(())
     }
 
-    fn are_geneds_fulfilled(&self) -> Result<bool> {
-        are_geneds_satisfied(self)
+    /// Credits `code` counts for in this schedule: the chosen value for variable-credit courses,
+    /// otherwise the catalog value.
+    pub fn course_credits(&self, code: &CourseCode) -> u32 {
+        match self.credits.get(code) {
+            Some(&chosen) => chosen,
+            None => self
+                .catalog
+                .courses
+                .get(code)
+                .map_or(0, |info| info.min_credits()),
+        }
+    }
+
+    pub fn validate(&mut self) -> Result<()> {
+        crate::model::two_stage_lex_schedule(self, crate::MAX_CREDITS_PER_SEMESTER)?;
+        Ok(())
     }
 
-    fn are_programs_valid(&self) -> Result<bool> {
-        let all_sched_codes = self
-            .courses
-            .iter()
+    /// The elective groups of the scheduled programs that this schedule does not satisfy, matched
+    /// the same way as GenEd requirements.
+    pub fn unmet_electives(&self) -> Result<Vec<UnmetElective>> {
+        let sched_courses: HashSet<&CourseCode> = std::iter::once(&self.incoming)
+            .chain(self.courses.iter())
             .flatten()
-            .collect::<HashSet<&CourseCode>>();
-        Ok(self
-            .programs
-            .iter()
-            .map(|prog_name| {
-                let prog = self
-                    .catalog
-                    .programs
-                    .iter()
-                    .find(|p| p.name == *prog_name)
-                    .ok_or_else(|| anyhow::anyhow!("Program {} not found in catalog", prog_name))?;
-                let all_prog_codes = prog
-                    .semesters
-                    .iter()
-                    .flatten()
-                    .collect::<HashSet<&CourseCode>>();
-                Ok(all_sched_codes.is_superset(&all_prog_codes))
-            })
-            .collect::<Result<Vec<_>>>()?
-            .iter()
-            .all(|x| *x))
+            .collect();
+        let mut unmet = Vec::new();
+        for prog_name in &self.programs {
+            let prog = self
+                .catalog
+                .programs
+                .iter()
+                .find(|p| p.name == *prog_name)
+                .ok_or_else(|| anyhow::anyhow!("Program {} not found in catalog", prog_name))?;
+            for elective in &prog.electives {
+                if satisfy_req(&elective.req, &sched_courses, self).is_none() {
+                    unmet.push(UnmetElective {
+                        program: prog.name.clone(),
+                        elective: elective.name.clone(),
+                    });
+                }
+            }
+        }
+        Ok(unmet)
     }
 
     pub fn validate_prereqs(&self) -> Result<bool> {
+        Ok(self.unmet_prereqs().is_empty())
+    }
+
+    /// Planned courses whose prerequisites are not met, with the index of their semester.
+    pub fn unmet_prereqs(&self) -> Vec<(usize, &CourseCode)> {
+        let mut unmet = Vec::new();
         for (sem_idx, sem) in self.courses.iter().enumerate() {
             for code in sem {
                 let req = self
@@ -248,11 +845,11 @@ impl Schedule {
                     .prereqs
                     .get(code)
                     .unwrap_or(&CourseReq::NotRequired);
-                if !req.is_satisfied(self, sem_idx) {
-                    return Ok(false);
+                if !req.is_satisfied(self, code, sem_idx) {
+                    unmet.push((sem_idx, code));
                 }
             }
         }
-        Ok(true)
+        unmet
     }
 }
@@ -1,3 +1,5 @@
+use crate::audit::Audit;
+use crate::catalog_diff::CatalogDiff;
 use crate::schedule::Schedule;
 use crate::{SAVEFILE_VERSION, TEMPLATE_PNG};
 use anyhow::Result;
@@ -20,7 +22,7 @@ fn pretty_print_sched_to_sheet(sched: &Schedule, sheet: &mut Worksheet) -> Resul
             (col_idx * 2) as u16,
             0,
             ((col_idx * 2) + 1) as u16,
-            &format!("Semester {col_idx}"),
+            &sched.term(col_idx - 1).to_string(),
             &center_format,
         )?;
     }
@@ -34,17 +36,12 @@ fn pretty_print_sched_to_sheet(sched: &Schedule, sheet: &mut Worksheet) -> Resul
                 .catalog
                 .courses
                 .get(val)
-                .map(|(_, x, _)| x.unwrap_or(0))
+                .map(|_| sched.course_credits(val))
                 .ok_or(anyhow::anyhow!("Course lookup not found: {}", val))?,
             &center_format,
         )?;
 
-        sem_sums[0] += sched
-            .catalog
-            .courses
-            .get(val)
-            .and_then(|(_, x, _)| *x)
-            .unwrap_or(0);
+        sem_sums[0] += sched.course_credits(val);
         last_row = last_row.max(row_idx);
     }
 
@@ -62,17 +59,12 @@ fn pretty_print_sched_to_sheet(sched: &Schedule, sheet: &mut Worksheet) -> Resul
                     .catalog
                     .courses
                     .get(val)
-                    .map(|(_, x, _)| x.unwrap_or(0))
+                    .map(|_| sched.course_credits(val))
                     .ok_or(anyhow::anyhow!("Course lookup not found: {}", val))?,
                 &center_format,
             )?;
 
-            sem_sums[col_idx] += sched
-                .catalog
-                .courses
-                .get(val)
-                .and_then(|(_, x, _)| *x)
-                .unwrap_or(0);
+            sem_sums[col_idx] += sched.course_credits(val);
             last_row = last_row.max(row_idx);
         }
     }
@@ -122,6 +114,102 @@ fn embed_schedule_in_sheet(sheet: &mut Worksheet, sched: &Schedule) -> Result<()
     Ok(())
 }
 
+fn pretty_print_catalog_diff_to_sheet(diff: &CatalogDiff, sheet: &mut Worksheet) -> Result<()> {
+    let header_format = Format::new().set_bold();
+
+    sheet.write_string_with_format(
+        0,
+        0,
+        format!(
+            "{}-{} to {}-{} catalog",
+            diff.old_year,
+            diff.old_year + 1,
+            diff.new_year,
+            diff.new_year + 1
+        ),
+        &header_format,
+    )?;
+    for (col_idx, title) in ["Change", "Item", "Old", "New"].iter().enumerate() {
+        sheet.write_string_with_format(2, col_idx as u16, *title, &header_format)?;
+    }
+    for (row_idx, change) in diff.changes.iter().enumerate() {
+        for (col_idx, val) in change.columns().iter().enumerate() {
+            sheet.write_string((row_idx + 3) as u32, col_idx as u16, val)?;
+        }
+    }
+
+    sheet.autofit();
+
+    Ok(())
+}
+
+fn pretty_print_audit_to_sheet(audit: &Audit, sheet: &mut Worksheet) -> Result<()> {
+    let header_format = Format::new().set_bold();
+    let unmet_format = Format::new().set_bold().set_font_color("#C00000");
+
+    for (col_idx, title) in [
+        "Type",
+        "Requirement",
+        "Status",
+        "Applied Courses",
+        "Missing",
+        "Notes",
+    ]
+    .iter()
+    .enumerate()
+    {
+        sheet.write_string_with_format(0, col_idx as u16, *title, &header_format)?;
+    }
+    for (row_idx, entry) in audit.entries.iter().enumerate() {
+        let row = (row_idx + 1) as u32;
+        sheet.write_string(row, 0, entry.kind.to_string())?;
+        sheet.write_string(row, 1, &entry.requirement)?;
+        let status = if entry.provisional {
+            format!("{} (provisional)", entry.status)
+        } else {
+            entry.status.to_string()
+        };
+        if entry.is_met() {
+            sheet.write_string(row, 2, status)?;
+        } else {
+            sheet.write_string_with_format(row, 2, status, &unmet_format)?;
+        }
+        sheet.write_string(
+            row,
+            3,
+            entry
+                .applied
+                .iter()
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )?;
+        if let Some(shortfall) = entry.shortfall {
+            sheet.write_string(row, 4, shortfall.to_string())?;
+        }
+        sheet.write_string(row, 5, entry.notes.join("; "))?;
+    }
+
+    sheet.autofit();
+
+    Ok(())
+}
+
+/// Add a "Catalog Changes" worksheet listing `diff` to `workbook`.
+pub fn add_catalog_diff_sheet(workbook: &mut Workbook, diff: &CatalogDiff) -> Result<()> {
+    let diff_sheet = workbook.add_worksheet().set_name("Catalog Changes")?;
+    pretty_print_catalog_diff_to_sheet(diff, diff_sheet)?;
+    diff_sheet.protect();
+    Ok(())
+}
+
+pub fn save_catalog_diff(fname: &PathBuf, diff: &CatalogDiff) -> Result<()> {
+    let mut workbook = Workbook::new();
+    add_catalog_diff_sheet(&mut workbook, diff)?;
+    workbook.save(fname)?;
+    Ok(())
+}
+
 pub fn save_schedule(fname: &PathBuf, sched: &Schedule) -> Result<()> {
     let mut workbook = Workbook::new();
 
@@ -129,6 +217,22 @@ pub fn save_schedule(fname: &PathBuf, sched: &Schedule) -> Result<()> {
     pretty_print_sched_to_sheet(sched, schedule_sheet)?;
     schedule_sheet.protect();
 
+    let audit_sheet = workbook.add_worksheet().set_name("Audit")?;
+    match sched.audit() {
+        Ok(audit) => pretty_print_audit_to_sheet(&audit, audit_sheet)?,
+        // The schedule is still worth saving, e.g. to fix an unknown program name later
+        Err(e) => {
+            let error_format = Format::new().set_bold().set_font_color("#C00000");
+            audit_sheet.write_string_with_format(
+                0,
+                0,
+                format!("Could not audit this schedule: {e:#}"),
+                &error_format,
+            )?;
+        }
+    }
+    audit_sheet.protect();
+
     let internal_sheet = workbook.add_worksheet().set_name("Internals")?;
     embed_schedule_in_sheet(internal_sheet, sched)?;
     internal_sheet.protect();
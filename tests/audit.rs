@@ -0,0 +1,61 @@
+use anyhow::Result;
+
+use ross_core::CC;
+use ross_core::audit::{Audit, AuditKind, Shortfall};
+use ross_core::load_catalogs::builtin_catalog;
+use ross_core::schedule::{CourseCode, generate_schedule};
+use ross_core::write_excel_file::save_schedule;
+
+#[test]
+fn test_audit_reports_unmet_elective() -> Result<()> {
+    let mut sched = generate_schedule(vec!["BA Chemistry"], builtin_catalog(), None)?;
+    let audit = sched.audit()?;
+    assert_eq!(audit.is_satisfied(), sched.is_valid()?);
+
+    let elective = audit
+        .entries
+        .iter()
+        .find(|e| e.requirement == "BA Chemistry: Advanced Courses")
+        .unwrap();
+    assert_eq!(elective.kind, AuditKind::ProgramElective);
+    assert!(!elective.is_met());
+    assert_eq!(elective.shortfall, Some(Shortfall::Courses(2)));
+
+    let last = sched.courses.len() - 1;
+    sched.courses[last].push(CC!("CHEM", 3250));
+    sched.courses[last].push(CC!("CHEM", 4350));
+    let audit = sched.audit()?;
+    let elective = audit
+        .entries
+        .iter()
+        .find(|e| e.requirement == "BA Chemistry: Advanced Courses")
+        .unwrap();
+    assert!(elective.is_met());
+    assert_eq!(elective.applied, vec![CC!("CHEM", 3250), CC!("CHEM", 4350)]);
+
+    // Every unmet entry explains itself
+    assert!(
+        audit
+            .unmet()
+            .all(|e| e.shortfall.is_some() || !e.notes.is_empty())
+    );
+
+    let json = serde_json::to_string(&audit)?;
+    assert_eq!(serde_json::from_str::<Audit>(&json)?, audit);
+    Ok(())
+}
+
+#[test]
+fn test_save_schedule_without_audit() -> Result<()> {
+    let mut sched = generate_schedule(vec!["BA Chemistry"], builtin_catalog(), None)?;
+    sched
+        .programs
+        .push("BA Underwater Basket Weaving".to_string());
+    assert!(sched.audit().is_err());
+
+    // The schedule is saved anyway, with the error in place of the audit
+    let fname = std::env::temp_dir().join(format!("ross_audit_{}.xlsx", std::process::id()));
+    let saved = save_schedule(&fname, &sched);
+    let _ = std::fs::remove_file(&fname);
+    saved
+}
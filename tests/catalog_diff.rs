@@ -0,0 +1,68 @@
+use anyhow::Result;
+
+use ross_core::CC;
+use ross_core::catalog_diff::{CatalogChange, catalog_diff};
+use ross_core::geneds::GenEd;
+use ross_core::load_catalogs::builtin_catalog;
+use ross_core::prereqs::CourseReq;
+use ross_core::schedule::{CourseCode, Credits};
+use ross_core::write_excel_file::save_catalog_diff;
+
+#[test]
+fn test_catalog_diff() -> Result<()> {
+    let old = builtin_catalog();
+    let mut new = builtin_catalog();
+    new.low_year = 2026;
+
+    assert!(catalog_diff(&old, &new).is_empty());
+
+    new.courses.get_mut(&CC!("CHEM", 1200)).unwrap().credits = Some(Credits::fixed(4));
+    let removed = new.courses.remove(&CC!("ARCH", 2300)).unwrap();
+    new.prereqs
+        .insert(CC!("CHEM", 1200), CourseReq::CoCourse(CC!("CHEM", 1201)));
+    new.programs[0].semesters[0].push(CC!("THEO", 1100));
+    if let Some(GenEd::Core { req, .. }) = new.geneds.first_mut() {
+        *req = ross_core::geneds::ElectiveReq::Courses {
+            num: 1,
+            courses: vec![CC!("ENGL", 1000)],
+        };
+    }
+
+    let diff = catalog_diff(&old, &new);
+    let expected = [
+        CatalogChange::CreditsChanged {
+            course: CC!("CHEM", 1200),
+            old: Some(Credits::fixed(3)),
+            new: Some(Credits::fixed(4)),
+        },
+        CatalogChange::CourseRemoved {
+            course: CC!("ARCH", 2300),
+            title: removed.title,
+        },
+        CatalogChange::PrereqChanged {
+            course: CC!("CHEM", 1200),
+            old: CourseReq::CoCourse(CC!("BIOL", 1121)),
+            new: CourseReq::CoCourse(CC!("CHEM", 1201)),
+        },
+        CatalogChange::ProgramSemesterChanged {
+            program: old.programs[0].name.clone(),
+            semester: 0,
+            added: vec![CC!("THEO", 1100)],
+            removed: vec![],
+        },
+        CatalogChange::GenEdCoursesChanged {
+            gened: "English Composition".to_string(),
+            added: vec![],
+            removed: vec![CC!("ENGL", 1010), CC!("HONR", 1030)],
+        },
+    ];
+    for change in &expected {
+        assert!(diff.changes.contains(change), "missing change: {change}");
+    }
+    assert_eq!(diff.changes.len(), expected.len(), "{diff}");
+
+    let fname = std::env::temp_dir().join(format!("ross_diff_{}.xlsx", std::process::id()));
+    save_catalog_diff(&fname, &diff)?;
+    std::fs::remove_file(&fname)?;
+    Ok(())
+}
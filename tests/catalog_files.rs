@@ -0,0 +1,101 @@
+use anyhow::Result;
+
+use ross_core::CC;
+use ross_core::load_catalogs::{CatalogRegistry, builtin_catalog};
+use ross_core::schedule::{Catalog, CourseCode, CourseCodeSuffix};
+
+#[test]
+fn test_catalog_dir_round_trip() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("ross_catalog_{}", std::process::id()));
+    let builtin = builtin_catalog();
+    builtin.save_dir(&dir)?;
+
+    let loaded = Catalog::from_dir(&dir)?;
+    std::fs::remove_dir_all(&dir)?;
+
+    assert_eq!(loaded.low_year, builtin.low_year);
+    assert_eq!(loaded.courses, builtin.courses);
+    assert_eq!(loaded.prereqs, builtin.prereqs);
+    assert_eq!(loaded.geneds, builtin.geneds);
+    assert_eq!(loaded.programs.len(), builtin.programs.len());
+    for prog in &builtin.programs {
+        let other = loaded
+            .programs
+            .iter()
+            .find(|p| p.name == prog.name)
+            .expect("program missing after round trip");
+        assert_eq!(other.semesters, prog.semesters);
+        assert_eq!(other.assoc_stems, prog.assoc_stems);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_course_code_from_str() -> Result<()> {
+    assert_eq!("chem-1200".parse::<CourseCode>()?, CC!("CHEM", 1200));
+    assert_eq!("CHEM-COMP".parse::<CourseCode>()?, CC!("CHEM", "COMP"));
+    assert!("CHEM1200".parse::<CourseCode>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_course_code_serde_round_trip() -> Result<()> {
+    let codes = [
+        CC!("CHEM", 1200),
+        CC!("CHEM", "COMP"),
+        CourseCode {
+            stem: "STEM".to_string(),
+            code: CourseCodeSuffix::Unique(1200),
+        },
+    ];
+    let json = serde_json::to_string(&codes)?;
+    assert_eq!(json, r#"["CHEM-1200","CHEM-COMP","STEM-#1200"]"#);
+    assert_eq!(serde_json::from_str::<Vec<CourseCode>>(&json)?, codes);
+    Ok(())
+}
+
+#[test]
+fn test_catalog_for_entry_year() {
+    let registry: CatalogRegistry = [2023, 2025, 2024]
+        .into_iter()
+        .map(|low_year| Catalog {
+            low_year,
+            ..builtin_catalog()
+        })
+        .collect();
+
+    assert_eq!(registry.years().collect::<Vec<_>>(), vec![2023, 2024, 2025]);
+    assert_eq!(
+        registry.catalog_for_entry_year(2024).map(|c| c.low_year),
+        Some(2024)
+    );
+    assert_eq!(
+        registry.catalog_for_entry_year(2030).map(|c| c.low_year),
+        Some(2025)
+    );
+    assert!(registry.catalog_for_entry_year(2022).is_none());
+
+    assert_eq!(registry.for_year(2024).map(|c| c.low_year), Some(2024));
+    assert!(registry.for_year(2030).is_none());
+    // Indexing still goes by position, as on any slice
+    assert_eq!(registry.get(0).map(|c| c.low_year), Some(2023));
+}
+
+#[test]
+fn test_catalog_load_errors() -> Result<()> {
+    let dir = std::env::temp_dir().join(format!("ross_bad_catalog_{}", std::process::id()));
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(dir.join("catalog.toml"), "low_year = \"not a year\"")?;
+
+    let mut registry = CatalogRegistry::from_iter([builtin_catalog()]);
+    let result = registry.load_dir(&dir);
+    std::fs::remove_dir_all(&dir)?;
+
+    let err = result.unwrap_err();
+    assert_eq!(err.dir, dir);
+    assert!(err.message.contains("catalog.toml"), "{err}");
+    assert_eq!(registry.load_errors(), [err]);
+    // The compiled-in catalog is still there
+    assert_eq!(registry.years().collect::<Vec<_>>(), vec![2025]);
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use anyhow::Result;
+use savefile::prelude::*;
+use savefile_derive::Savefile;
+use std::collections::HashMap;
+
+use ross_core::CC;
+use ross_core::SAVEFILE_VERSION;
+use ross_core::geneds::GenEd;
+use ross_core::prereqs::CourseReq;
+use ross_core::schedule::{
+    Catalog, CourseCode, CourseInfo, CourseTermOffering, Program, Schedule, Season, SummerOptions,
+    Term,
+};
+
+// The layout of a schedule as embedded in workbooks by 0.1.x
+#[derive(Savefile)]
+struct OldCatalog {
+    programs: Vec<Program>,
+    geneds: Vec<GenEd>,
+    prereqs: HashMap<CourseCode, CourseReq>,
+    courses: HashMap<CourseCode, (String, Option<u32>, CourseTermOffering)>,
+    low_year: u32,
+}
+
+#[derive(Savefile)]
+struct OldSchedule {
+    courses: Vec<Vec<CourseCode>>,
+    programs: Vec<String>,
+    incoming: Vec<CourseCode>,
+    catalog: OldCatalog,
+}
+
+const OLD_SAVEFILE_VERSION: u32 = 1002;
+
+#[test]
+fn test_load_old_savefile() -> Result<()> {
+    let old = OldSchedule {
+        courses: vec![vec![CC!("CHEM", 1200)], vec![CC!("CHEM", 1210)]],
+        programs: vec![],
+        incoming: vec![CC!("MATH", 1300)],
+        catalog: OldCatalog {
+            programs: vec![],
+            geneds: vec![],
+            prereqs: HashMap::from([
+                (CC!("CHEM", 1210), CourseReq::PreCourse(CC!("CHEM", 1200))),
+                (CC!("CHEM", 1200), CourseReq::Instructor),
+            ]),
+            courses: HashMap::from([(
+                CC!("CHEM", 1200),
+                (
+                    "General Chemistry I".to_string(),
+                    Some(4),
+                    CourseTermOffering::Fall,
+                ),
+            )]),
+            low_year: 2025,
+        },
+    };
+    let bytes = save_to_mem(OLD_SAVEFILE_VERSION, &old)?;
+    let sched: Schedule = load_from_mem(&bytes, SAVEFILE_VERSION)?;
+
+    assert_eq!(sched.courses, old.courses);
+    assert_eq!(sched.incoming, old.incoming);
+    assert_eq!(sched.catalog.prereqs, old.catalog.prereqs);
+    assert_eq!(
+        sched.catalog.courses[&CC!("CHEM", 1200)],
+        CourseInfo::from_listing(
+            &CC!("CHEM", 1200),
+            "General Chemistry I",
+            Some(4),
+            CourseTermOffering::Fall
+        )
+    );
+    assert_eq!(sched.start, Term::new(Season::Fall, 2025));
+    assert_eq!(sched.summer, SummerOptions::default());
+    assert!(sched.credits.is_empty() && sched.consents.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_load_old_json() -> Result<()> {
+    let json = r#"{
+        "courses": [["CHEM-1200"]],
+        "programs": [],
+        "incoming": [],
+        "catalog": {
+            "programs": [],
+            "geneds": [],
+            "prereqs": {},
+            "courses": {
+                "CHEM-1200": ["General Chemistry I", 4, "Fall"],
+                "CHEM-1201": ["General Chemistry I Lab", 1, "Fall"]
+            },
+            "low_year": 2025
+        }
+    }"#;
+    let sched: Schedule = serde_json::from_str(json)?;
+    assert_eq!(sched.start, Term::new(Season::Fall, 2025));
+    let lecture = &sched.catalog.courses[&CC!("CHEM", 1200)];
+    assert_eq!(lecture.min_credits(), 4);
+    assert_eq!(lecture.level, Some(1000));
+    assert!(!lecture.lab);
+    let lab = &sched.catalog.courses[&CC!("CHEM", 1201)];
+    assert_eq!(lab.level, Some(1000));
+    assert!(lab.lab);
+
+    // Current plans round trip
+    let json = serde_json::to_string(&sched)?;
+    let again: Schedule = serde_json::from_str(&json)?;
+    assert_eq!(again, sched);
+    Ok(())
+}
+
+#[test]
+fn test_cross_listed_credits() -> Result<()> {
+    let listing =
+        |title: &str, credits| CourseInfo::new(title, Some(credits), CourseTermOffering::Both);
+    let mut history = listing("Ancient History", 3);
+    history.cross_listed = vec![CC!("CLAS", 2000)];
+    let mut classics = listing("Ancient History", 3);
+    classics.cross_listed = vec![CC!("HIST", 2000)];
+    let mut ensemble = listing("Choir", 1);
+    ensemble.repeatable = true;
+    let catalog = Catalog {
+        courses: HashMap::from([
+            (CC!("HIST", 2000), history),
+            (CC!("CLAS", 2000), classics),
+            (CC!("MUSC", 1000), ensemble),
+        ]),
+        ..Catalog::new(2025)
+    };
+    let mut sched: Schedule = serde_json::from_value(serde_json::json!({
+        "courses": [
+            ["HIST-2000", "MUSC-1000"],
+            ["CLAS-2000", "MUSC-1000"],
+            [],
+        ],
+        "programs": [],
+        "incoming": [],
+        "catalog": {"programs": [], "geneds": [], "prereqs": {}, "courses": {}, "low_year": 2025},
+    }))?;
+    sched.catalog = catalog;
+
+    assert!(
+        sched
+            .catalog
+            .is_same_course(&CC!("CLAS", 2000), &CC!("HIST", 2000))
+    );
+    // The cross-listing earns no credit again, the repeatable ensemble does
+    assert_eq!(sched.credits_before(2), 5);
+    sched.reduce()?;
+    assert_eq!(sched.courses[1], vec![CC!("MUSC", 1000)]);
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use anyhow::Result;
+
+use ross_core::CC;
+use ross_core::audit::AuditKind;
+use ross_core::load_catalogs::builtin_catalog;
+use ross_core::schedule::{CourseCode, Schedule, generate_schedule};
+
+#[test]
+fn test_instructor_consent() -> Result<()> {
+    // CHEM-3150 requires instructor consent or CHEM-1210, MATH-1350 and PHYS-2110
+    let mut sched = generate_schedule(vec!["BA Chemistry"], builtin_catalog(), None)?;
+    sched.courses[0].push(CC!("CHEM", 3150));
+    let req = sched.catalog.prereqs[&CC!("CHEM", 3150)].clone();
+
+    let prereq_entry = |sched: &Schedule| -> Result<_> {
+        Ok(sched
+            .audit()?
+            .entries
+            .into_iter()
+            .find(|e| e.kind == AuditKind::Prereq && e.requirement == "CHEM-3150")
+            .unwrap())
+    };
+
+    assert!(!req.is_satisfied(&sched, &CC!("CHEM", 3150), 0));
+    assert!(!prereq_entry(&sched)?.is_met());
+
+    sched.grant_consent(CC!("CHEM", 3150));
+    assert!(req.is_satisfied(&sched, &CC!("CHEM", 3150), 0));
+    // Consent is for one course only
+    assert!(!req.is_satisfied(&sched, &CC!("CHEM", 4350), 0));
+    let explanation = req.explain(&sched, &CC!("CHEM", 3150), 0);
+    assert!(explanation.by_consent);
+    let entry = prereq_entry(&sched)?;
+    assert!(entry.is_met());
+    assert_eq!(entry.notes, vec!["depends on instructor consent"]);
+
+    // Once the listed courses are taken the consent is no longer needed
+    let last = sched.courses.len() - 1;
+    let course = sched.courses[0].pop().unwrap();
+    sched.courses[last].push(course);
+    let explanation = req.explain(&sched, &CC!("CHEM", 3150), last);
+    assert!(explanation.satisfied);
+    assert!(!explanation.by_consent);
+
+    sched.revoke_consent(&CC!("CHEM", 3150));
+    assert!(!sched.has_consent(&CC!("CHEM", 3150)));
+    Ok(())
+}
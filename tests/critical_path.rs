@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use ross_core::CC;
+use ross_core::critical_path::{Availability, CriticalPath};
+use ross_core::prereqs::CourseReq::*;
+use ross_core::schedule::{
+    Catalog, CourseCode, CourseInfo, CourseTermOffering, Program, Season, Term,
+};
+
+fn test_catalog() -> Catalog {
+    use CourseTermOffering::*;
+    Catalog {
+        programs: vec![Program {
+            name: "BS Test".to_string(),
+            semesters: vec![
+                vec![CC!("MATH", 1300), CC!("CHEM", 1200), CC!("CHEM", 1201)],
+                vec![CC!("MATH", 1310)],
+                vec![CC!("TEST", 2000)],
+                vec![CC!("TEST", 3000), CC!("TEST", 1000)],
+            ],
+            electives: vec![],
+            assoc_stems: vec!["TEST".to_string()],
+        }],
+        prereqs: HashMap::from([
+            (CC!("MATH", 1310), PreCourse(CC!("MATH", 1300))),
+            (
+                CC!("TEST", 2000),
+                And(vec![
+                    PreCourse(CC!("MATH", 1310)),
+                    Or(vec![PreCourse(CC!("TEST", 1000)), Instructor]),
+                ]),
+            ),
+            (CC!("TEST", 3000), PreCourse(CC!("TEST", 2000))),
+            (CC!("CHEM", 1200), CoCourse(CC!("CHEM", 1201))),
+            (CC!("CHEM", 1201), CoCourse(CC!("CHEM", 1200))),
+            (CC!("LOOP", 1000), PreCourse(CC!("LOOP", 2000))),
+            (CC!("LOOP", 2000), PreCourse(CC!("LOOP", 1000))),
+        ]),
+        courses: [
+            (CC!("MATH", 1300), Both),
+            (CC!("MATH", 1310), Both),
+            (CC!("TEST", 1000), Both),
+            (CC!("TEST", 2000), Fall),
+            (CC!("TEST", 3000), Both),
+            (CC!("CHEM", 1200), Fall),
+            (CC!("CHEM", 1201), Both),
+        ]
+        .into_iter()
+        .map(|(code, offering)| (code, CourseInfo::new("Course", Some(3), offering)))
+        .collect(),
+        ..Catalog::new(2025)
+    }
+}
+
+// Fall 2025 through Spring 2027
+fn terms(num: usize) -> Vec<Term> {
+    std::iter::successors(Some(Term::new(Season::Fall, 2025)), |t| {
+        Some(t.next_semester())
+    })
+    .take(num)
+    .collect()
+}
+
+#[test]
+fn test_earliest_terms() {
+    let catalog = test_catalog();
+    let path = CriticalPath::new(&catalog, &[CC!("GNST", 1000)], terms(4));
+
+    assert_eq!(path.earliest(&CC!("GNST", 1000)), Availability::Incoming);
+    assert_eq!(path.earliest(&CC!("MATH", 1300)), Availability::Semester(0));
+    assert_eq!(path.earliest(&CC!("MATH", 1310)), Availability::Semester(1));
+    // Ready in Fall 2026, since it is not offered in Spring
+    assert_eq!(
+        path.earliest_term(&CC!("TEST", 2000)),
+        Some(Term::new(Season::Fall, 2026))
+    );
+    assert_eq!(path.earliest(&CC!("TEST", 3000)), Availability::Semester(3));
+    // Lecture and lab co-require each other and share a term
+    assert_eq!(path.earliest(&CC!("CHEM", 1201)), Availability::Semester(0));
+    assert_eq!(path.earliest(&CC!("LOOP", 1000)), Availability::Unreachable);
+
+    assert_eq!(
+        path.chain(&CC!("TEST", 3000)),
+        vec![
+            CC!("MATH", 1300),
+            CC!("MATH", 1310),
+            CC!("TEST", 2000),
+            CC!("TEST", 3000)
+        ]
+    );
+
+    // Starting calculus a semester late pushes the sequence out of the four semesters
+    let late = CriticalPath::with_not_before(
+        &catalog,
+        &[],
+        terms(4),
+        &HashMap::from([(CC!("MATH", 1300), 1)]),
+    );
+    assert_eq!(late.earliest(&CC!("TEST", 2000)), Availability::Unreachable);
+}
+
+#[test]
+fn test_program_path() {
+    let catalog = test_catalog();
+    let prog = &catalog.programs[0];
+
+    let path = CriticalPath::new(&catalog, &[], terms(4)).program_path(prog);
+    assert!(path.is_feasible(), "{path}");
+    assert_eq!(path.longest_chain.len(), 4);
+    assert_eq!(path.longest_chain.last(), Some(&CC!("TEST", 3000)));
+    let slack = |code: CourseCode| {
+        path.slack
+            .iter()
+            .find(|s| s.code == code)
+            .and_then(|s| s.slack())
+    };
+    assert_eq!(slack(CC!("MATH", 1300)), Some(0));
+    assert_eq!(slack(CC!("TEST", 3000)), Some(0));
+    // Only one of TEST-1000 or instructor consent is needed, so TEST-1000 is not on the chain
+    assert_eq!(slack(CC!("TEST", 1000)), Some(3));
+    assert_eq!(slack(CC!("CHEM", 1200)), Some(2));
+
+    // Calculus already taken buys a semester
+    let path = CriticalPath::new(&catalog, &[CC!("MATH", 1300)], terms(4)).program_path(prog);
+    assert_eq!(
+        path.slack
+            .iter()
+            .find(|s| s.code == CC!("MATH", 1310))
+            .unwrap()
+            .slack(),
+        Some(1)
+    );
+
+    // Three semesters are not enough
+    let path = CriticalPath::new(&catalog, &[], terms(3)).program_path(prog);
+    assert!(!path.is_feasible());
+}
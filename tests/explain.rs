@@ -0,0 +1,60 @@
+use ross_core::CC;
+use ross_core::prereqs::{CourseReq, MissingCourse};
+use ross_core::schedule::{Catalog, CourseCode, Schedule, Season, Term};
+
+fn test_schedule(courses: Vec<Vec<CourseCode>>) -> Schedule {
+    Schedule {
+        incoming: vec![CC!("MATH", 1300)],
+        ..Schedule::new(Catalog::new(2025), courses)
+    }
+}
+
+#[test]
+fn test_explain_failing_branch() {
+    let req = CourseReq::And(vec![
+        CourseReq::Or(vec![
+            CourseReq::PreCourse(CC!("MATH", 1300)),
+            CourseReq::PreCourse(CC!("MATH", 1350)),
+        ]),
+        CourseReq::CoCourse(CC!("PHYS", 2110)),
+    ]);
+    let sched = test_schedule(vec![vec![], vec![CC!("PHYS", 2100)]]);
+
+    let explanation = req.explain(&sched, &CC!("PHYS", 2120), 1);
+    assert!(!explanation.satisfied);
+    assert_eq!(
+        explanation.satisfied,
+        req.is_satisfied(&sched, &CC!("PHYS", 2120), 1)
+    );
+    assert!(explanation.children[0].satisfied);
+    assert!(!explanation.children[1].satisfied);
+    assert_eq!(
+        explanation.missing_courses(),
+        vec![&MissingCourse {
+            code: CC!("PHYS", 2110),
+            semester: Some(1),
+            term: Some(Term::new(Season::Spring, 2026)),
+            same_term: false,
+        }]
+    );
+    assert_eq!(explanation.applied_courses(), vec![&CC!("MATH", 1300)]);
+}
+
+#[test]
+fn test_explain_prior_course_deadline() {
+    let req = CourseReq::PreCourse(CC!("CHEM", 1210));
+
+    // A prior course for the first semester would have had to be incoming credit
+    let sched = test_schedule(vec![vec![CC!("CHEM", 1210)]]);
+    let explanation = req.explain(&sched, &CC!("PHYS", 2120), 0);
+    assert!(!explanation.satisfied);
+    assert_eq!(
+        explanation.missing.map(|m| (m.semester, m.term)),
+        Some((None, None))
+    );
+
+    let sched = test_schedule(vec![vec![CC!("CHEM", 1210)], vec![]]);
+    let explanation = req.explain(&sched, &CC!("PHYS", 2120), 1);
+    assert!(explanation.satisfied);
+    assert_eq!(explanation.missing, None);
+}
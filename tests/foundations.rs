@@ -0,0 +1,352 @@
+use anyhow::Result;
+use std::collections::HashSet;
+
+use ross_core::CC;
+use ross_core::audit::{AuditKind, Shortfall};
+use ross_core::geneds::{ElectiveReq, GenEd, are_geneds_satisfied};
+use ross_core::load_catalogs::builtin_catalog;
+use ross_core::schedule::{
+    Catalog, CourseCode, CourseInfo, CourseTermOffering, Schedule, generate_schedule,
+};
+
+fn foundation(name: &str, req: ElectiveReq) -> GenEd {
+    GenEd::Foundation {
+        name: name.to_string(),
+        req,
+    }
+}
+
+fn test_schedule(courses: Vec<CourseCode>) -> Schedule {
+    let catalog = Catalog {
+        geneds: vec![
+            foundation(
+                "Historical Inquiry",
+                ElectiveReq::Credits {
+                    num: 6,
+                    courses: vec![CC!("ARCH", 2300), CC!("ARCH", 2301), CC!("HIST", 1000)],
+                },
+            ),
+            foundation(
+                "Aesthetic Experience",
+                ElectiveReq::Credits {
+                    num: 3,
+                    courses: vec![CC!("ARCH", 2300), CC!("ARCH", 2301)],
+                },
+            ),
+            foundation(
+                "Natural World",
+                ElectiveReq::Courses {
+                    num: 1,
+                    courses: vec![CC!("BIOL", 1050), CC!("HIST", 1000)],
+                },
+            ),
+        ],
+        courses: [
+            (CC!("ARCH", 2300), 3),
+            (CC!("ARCH", 2301), 3),
+            (CC!("HIST", 1000), 3),
+            (CC!("BIOL", 1050), 4),
+        ]
+        .into_iter()
+        .map(|(code, credits)| {
+            (
+                code,
+                CourseInfo::new("Course", Some(credits), CourseTermOffering::Both),
+            )
+        })
+        .collect(),
+        ..Catalog::new(2025)
+    };
+    Schedule::new(catalog, vec![courses])
+}
+
+#[test]
+fn test_foundation_assignment() -> Result<()> {
+    // Historical Inquiry alone would take both ARCH courses, leaving nothing for Aesthetic Experience
+    let sched = test_schedule(vec![
+        CC!("ARCH", 2300),
+        CC!("ARCH", 2301),
+        CC!("HIST", 1000),
+        CC!("BIOL", 1050),
+    ]);
+    let assignment = sched.foundation_assignment();
+    assert!(assignment.is_complete());
+    let applied: Vec<_> = assignment
+        .foundations
+        .iter()
+        .map(|f| (f.name, f.applied.clone()))
+        .collect();
+    assert_eq!(
+        applied,
+        vec![
+            (
+                "Historical Inquiry",
+                vec![CC!("ARCH", 2300), CC!("HIST", 1000)]
+            ),
+            ("Aesthetic Experience", vec![CC!("ARCH", 2301)]),
+            ("Natural World", vec![CC!("BIOL", 1050)]),
+        ]
+    );
+    assert_eq!(
+        assignment.foundation_of(&CC!("ARCH", 2301)),
+        Some("Aesthetic Experience")
+    );
+    assert!(are_geneds_satisfied(&sched)?);
+
+    // Without the biology course, HIST-1000 can only count once
+    let sched = test_schedule(vec![
+        CC!("ARCH", 2300),
+        CC!("ARCH", 2301),
+        CC!("HIST", 1000),
+    ]);
+    let assignment = sched.foundation_assignment();
+    assert!(!assignment.is_complete());
+    assert_eq!(
+        assignment
+            .foundations
+            .iter()
+            .filter_map(|f| f.shortfall)
+            .collect::<Vec<_>>(),
+        vec![Shortfall::Courses(1)]
+    );
+    assert!(!are_geneds_satisfied(&sched)?);
+
+    // The audit shows which course counted where, and why a Foundation met on its own is not
+    let audit = sched.audit()?;
+    let unmet: Vec<_> = audit
+        .entries
+        .iter()
+        .filter(|e| e.kind == AuditKind::Foundation && !e.is_met())
+        .collect();
+    assert_eq!(unmet.len(), 1);
+    assert_eq!(
+        unmet[0].notes,
+        vec!["its courses are already counted towards another Foundation"]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_foundation_assignment_builtin() -> Result<()> {
+    let sched = generate_schedule(vec!["BA Chemistry"], builtin_catalog(), None)?;
+    let assignment = sched.foundation_assignment();
+    let mut seen = HashSet::new();
+    for foundation in &assignment.foundations {
+        for code in &foundation.applied {
+            assert!(seen.insert(code), "{code} counts twice");
+        }
+    }
+
+    let audit = sched.audit()?;
+    let entries: Vec<_> = audit
+        .entries
+        .iter()
+        .filter(|e| e.kind == AuditKind::Foundation)
+        .collect();
+    assert_eq!(entries.len(), assignment.foundations.len());
+    for (entry, foundation) in entries.iter().zip(&assignment.foundations) {
+        assert_eq!(entry.requirement, foundation.name);
+        assert_eq!(entry.applied, foundation.applied);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_foundation_set_options() {
+    // Only the second option leaves ARCH-2300 for the other Foundation
+    let mut sched = test_schedule(vec![CC!("ARCH", 2300), CC!("ARCH", 2301)]);
+    sched.catalog.geneds = vec![
+        foundation(
+            "Aesthetic Experience",
+            ElectiveReq::SetOpts(vec![vec![CC!("ARCH", 2300)], vec![CC!("ARCH", 2301)]]),
+        ),
+        foundation(
+            "Historical Inquiry",
+            ElectiveReq::Set(vec![CC!("ARCH", 2300)]),
+        ),
+    ];
+    let assignment = sched.foundation_assignment();
+    assert!(assignment.is_complete());
+    assert_eq!(
+        assignment.foundation_of(&CC!("ARCH", 2301)),
+        Some("Aesthetic Experience")
+    );
+    assert_eq!(
+        assignment.foundation_of(&CC!("ARCH", 2300)),
+        Some("Historical Inquiry")
+    );
+}
+
+#[test]
+fn test_foundation_credit_conflicts() {
+    // Historical Inquiry needs the 4-credit course and one of the ARCH courses, Aesthetic Experience
+    // the other ARCH course, and Natural World one course of its own
+    let geneds = vec![
+        foundation(
+            "Historical Inquiry",
+            ElectiveReq::Credits {
+                num: 7,
+                courses: vec![CC!("ARCH", 2300), CC!("ARCH", 2301), CC!("BIOL", 1050)],
+            },
+        ),
+        foundation(
+            "Aesthetic Experience",
+            ElectiveReq::Credits {
+                num: 3,
+                courses: vec![CC!("ARCH", 2300), CC!("ARCH", 2301)],
+            },
+        ),
+        foundation(
+            "Natural World",
+            ElectiveReq::Courses {
+                num: 1,
+                courses: vec![CC!("ARCH", 2301), CC!("HIST", 1000)],
+            },
+        ),
+    ];
+    let mut sched = test_schedule(vec![
+        CC!("ARCH", 2300),
+        CC!("ARCH", 2301),
+        CC!("HIST", 1000),
+        CC!("BIOL", 1050),
+    ]);
+    sched.catalog.geneds = geneds;
+    let assignment = sched.foundation_assignment();
+    assert!(assignment.is_complete());
+    let applied: Vec<_> = assignment
+        .foundations
+        .iter()
+        .map(|f| f.applied.clone())
+        .collect();
+    assert_eq!(
+        applied,
+        vec![
+            vec![CC!("ARCH", 2300), CC!("BIOL", 1050)],
+            vec![CC!("ARCH", 2301)],
+            vec![CC!("HIST", 1000)],
+        ]
+    );
+
+    // Without HIST-1000 one Foundation has to go short; the ones listed first are met
+    sched.courses = vec![vec![
+        CC!("ARCH", 2300),
+        CC!("ARCH", 2301),
+        CC!("BIOL", 1050),
+    ]];
+    let assignment = sched.foundation_assignment();
+    assert_eq!(
+        assignment
+            .foundations
+            .iter()
+            .map(|f| f.shortfall)
+            .collect::<Vec<_>>(),
+        vec![None, None, Some(Shortfall::Courses(1))]
+    );
+}
+
+/// A schedule of `num` one-credit ARTS courses, with the Foundations in `geneds`.
+fn arts_schedule(num: usize, geneds: Vec<GenEd>) -> Schedule {
+    let mut sched = test_schedule((0..num).map(|i| CC!("ARTS", 1000 + i)).collect());
+    for i in 0..num {
+        sched.catalog.courses.insert(
+            CC!("ARTS", 1000 + i),
+            CourseInfo::new("Studio", Some(1), CourseTermOffering::Both),
+        );
+    }
+    sched.catalog.geneds = geneds;
+    sched
+}
+
+#[test]
+fn test_foundation_colliding_covers() -> Result<()> {
+    // Pairing the 4-credit course with any one ARTS course covers Historical Inquiry, but Natural
+    // World needs it, so only a cover of five ARTS courses leaves both met
+    let mut courses = vec![CC!("BIOL", 1050)];
+    courses.extend((0..70).map(|i| CC!("ARTS", 1000 + i)));
+    let mut sched = arts_schedule(
+        70,
+        vec![
+            foundation(
+                "Historical Inquiry",
+                ElectiveReq::Credits {
+                    num: 5,
+                    courses: courses.clone(),
+                },
+            ),
+            foundation(
+                "Natural World",
+                ElectiveReq::Courses {
+                    num: 1,
+                    courses: vec![CC!("BIOL", 1050)],
+                },
+            ),
+        ],
+    );
+    sched.courses[0].push(CC!("BIOL", 1050));
+    let assignment = sched.foundation_assignment();
+    assert!(assignment.is_complete());
+    assert!(!assignment.undetermined);
+    assert_eq!(
+        assignment.foundations[0].applied,
+        (0..5).map(|i| CC!("ARTS", 1000 + i)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        assignment.foundation_of(&CC!("BIOL", 1050)),
+        Some("Natural World")
+    );
+    assert!(are_geneds_satisfied(&sched)?);
+    Ok(())
+}
+
+#[test]
+fn test_foundation_search_undetermined() -> Result<()> {
+    // The two Foundations cannot share 60 credits, but there are too many ways to cover the first
+    // to rule them all out
+    let courses: Vec<_> = (0..60).map(|i| CC!("ARTS", 1000 + i)).collect();
+    let sched = arts_schedule(
+        60,
+        vec![
+            foundation(
+                "Aesthetic Experience",
+                ElectiveReq::Credits {
+                    num: 30,
+                    courses: courses.clone(),
+                },
+            ),
+            foundation(
+                "Historical Inquiry",
+                ElectiveReq::Credits { num: 31, courses },
+            ),
+        ],
+    );
+    let assignment = sched.foundation_assignment();
+    assert!(assignment.undetermined);
+    assert_eq!(
+        assignment
+            .foundations
+            .iter()
+            .map(|f| f.shortfall)
+            .collect::<Vec<_>>(),
+        vec![None, Some(Shortfall::Credits(1))]
+    );
+    let err = are_geneds_satisfied(&sched).unwrap_err();
+    assert!(
+        err.to_string().contains("Historical Inquiry unmet"),
+        "{err}"
+    );
+
+    let audit = sched.audit()?;
+    let unmet = audit
+        .entries
+        .iter()
+        .find(|e| e.requirement == "Historical Inquiry")
+        .unwrap();
+    assert_eq!(
+        unmet.notes,
+        vec![
+            "its courses are already counted towards another Foundation",
+            "the search for a course assignment ran out of steps before finding whether it can be met",
+        ]
+    );
+    Ok(())
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+use ross_core::CC;
+use ross_core::geneds::{ElectiveReq, GenEd};
+use ross_core::lint::{CodeSource, LintFinding};
+use ross_core::prereqs::CourseReq::*;
+use ross_core::schedule::{Catalog, CourseCode, CourseInfo, CourseTermOffering, Elective, Program};
+
+fn test_catalog() -> Catalog {
+    Catalog {
+        programs: vec![Program {
+            name: "BS Test".to_string(),
+            semesters: vec![vec![
+                CC!("TEST", 1000),
+                CC!("TEST", 1001),
+                CC!("TEST", 1500),
+            ]],
+            electives: vec![Elective {
+                name: "Extras".to_string(),
+                req: ElectiveReq::Courses {
+                    num: 1,
+                    courses: vec![CC!("TEST", 2000), CC!("TEST", 9999)],
+                },
+            }],
+            assoc_stems: vec!["TEST".to_string()],
+        }],
+        geneds: vec![GenEd::Core {
+            name: "Writing".to_string(),
+            req: ElectiveReq::Set(vec![CC!("ENGL", 1000)]),
+        }],
+        prereqs: HashMap::from([
+            (CC!("TEST", 1001), CoCourse(CC!("TEST", 1000))),
+            (CC!("TEST", 2000), PreCourse(CC!("TEST", 2100))),
+            (
+                CC!("TEST", 2100),
+                And(vec![
+                    PreCourse(CC!("TEST", 2000)),
+                    PreCourse(CC!("TEST", 404)),
+                ]),
+            ),
+        ]),
+        courses: [
+            (
+                CC!("TEST", 1000),
+                "Testing I",
+                Some(3),
+                CourseTermOffering::Fall,
+            ),
+            (
+                CC!("TEST", 1001),
+                "Testing I Lab",
+                Some(1),
+                CourseTermOffering::Fall,
+            ),
+            (
+                CC!("TEST", 1500),
+                "Summer Testing",
+                None,
+                CourseTermOffering::Summer,
+            ),
+            (
+                CC!("TEST", 2000),
+                "Testing II",
+                Some(3),
+                CourseTermOffering::Both,
+            ),
+            (
+                CC!("TEST", 2100),
+                "Testing III",
+                Some(3),
+                CourseTermOffering::Both,
+            ),
+        ]
+        .into_iter()
+        .map(|(code, title, credits, offering)| {
+            let info = CourseInfo::from_listing(&code, title, credits, offering);
+            (code, info)
+        })
+        .collect(),
+        low_year: 2025,
+    }
+}
+
+#[test]
+fn test_lint_findings() {
+    let findings = test_catalog().lint();
+    let expected = [
+        LintFinding::DanglingPrereq {
+            course: CC!("TEST", 2100),
+            missing: CC!("TEST", 404),
+        },
+        LintFinding::PrereqCycle {
+            courses: vec![CC!("TEST", 2000), CC!("TEST", 2100)],
+        },
+        LintFinding::MissingCredits {
+            course: CC!("TEST", 1500),
+        },
+        LintFinding::UnknownCourse {
+            course: CC!("TEST", 9999),
+            source: CodeSource::ProgramElective {
+                program: "BS Test".to_string(),
+                elective: "Extras".to_string(),
+            },
+        },
+        LintFinding::UnknownCourse {
+            course: CC!("ENGL", 1000),
+            source: CodeSource::GenEd("Writing".to_string()),
+        },
+        LintFinding::AsymmetricCorequisite {
+            course: CC!("TEST", 1001),
+            partner: CC!("TEST", 1000),
+        },
+        LintFinding::SummerCourseInProgram {
+            program: "BS Test".to_string(),
+            semester: 0,
+            course: CC!("TEST", 1500),
+        },
+    ];
+    for finding in &expected {
+        assert!(findings.contains(finding), "missing finding: {finding}");
+    }
+    assert_eq!(findings.len(), expected.len(), "{findings:#?}");
+}
+
+#[test]
+fn test_lint_ignores_corequisite_pairs() {
+    let mut catalog = test_catalog();
+    catalog
+        .prereqs
+        .insert(CC!("TEST", 1000), CoCourse(CC!("TEST", 1001)));
+    let findings = catalog.lint();
+    assert!(
+        !findings
+            .iter()
+            .any(|f| matches!(f, LintFinding::AsymmetricCorequisite { .. })),
+        "{findings:#?}"
+    );
+    assert!(
+        !findings
+            .iter()
+            .any(|f| matches!(f, LintFinding::PrereqCycle { courses } if courses.contains(&CC!("TEST", 1000)))),
+        "{findings:#?}"
+    );
+}
+
+#[test]
+fn test_lint_same_term_candidates() {
+    let mut catalog = test_catalog();
+    catalog
+        .prereqs
+        .insert(CC!("TEST", 1000), CoCourse(CC!("TEST", 1001)));
+    let candidate = LintFinding::SameTermCandidate {
+        lecture: CC!("TEST", 1000),
+        lab: CC!("TEST", 1001),
+    };
+    assert!(catalog.lint().contains(&candidate));
+
+    catalog
+        .prereqs
+        .insert(CC!("TEST", 1000), SameTermCourse(CC!("TEST", 1001)));
+    catalog
+        .prereqs
+        .insert(CC!("TEST", 1001), SameTermCourse(CC!("TEST", 1000)));
+    let findings = catalog.lint();
+    assert!(!findings.contains(&candidate), "{findings:#?}");
+    assert!(
+        !findings
+            .iter()
+            .any(|f| matches!(f, LintFinding::AsymmetricCorequisite { .. })),
+        "{findings:#?}"
+    );
+}
+
+#[test]
+fn test_lint_cross_listings() {
+    let mut catalog = test_catalog();
+    let info = catalog.courses.get_mut(&CC!("TEST", 2000)).unwrap();
+    info.cross_listed = vec![CC!("TEST", 2100), CC!("HIST", 2000)];
+    let findings = catalog.lint();
+    assert!(findings.contains(&LintFinding::OneWayCrossListing {
+        course: CC!("TEST", 2000),
+        other: CC!("TEST", 2100),
+    }));
+    assert!(findings.contains(&LintFinding::UnknownCourse {
+        course: CC!("HIST", 2000),
+        source: CodeSource::CrossListing(CC!("TEST", 2000)),
+    }));
+
+    let info = catalog.courses.get_mut(&CC!("TEST", 2100)).unwrap();
+    info.cross_listed = vec![CC!("TEST", 2000)];
+    assert!(
+        !catalog
+            .lint()
+            .iter()
+            .any(|f| matches!(f, LintFinding::OneWayCrossListing { .. }))
+    );
+}
@@ -0,0 +1,85 @@
+use ross_core::load_catalogs::builtin_catalog;
+use ross_core::prereqs::CourseReq::{self, *};
+use ross_core::prereqs::{Grade, GradeLetter, GradeQualifier, Standing as ClassStanding};
+use ross_core::schedule::CourseCode;
+use ross_core::{CC, GR};
+
+fn pre(num: usize) -> CourseReq {
+    PreCourse(CC!("TEST", num))
+}
+
+#[test]
+fn test_normalize() {
+    assert_eq!(And(vec![pre(1000)]).normalize(), pre(1000));
+    assert_eq!(
+        And(vec![And(vec![pre(1000), pre(1010)]), pre(1020), pre(1000)]).normalize(),
+        And(vec![pre(1000), pre(1010), pre(1020)])
+    );
+    assert_eq!(
+        Or(vec![pre(1000), Or(vec![pre(1010), pre(1000)])]).normalize(),
+        Or(vec![pre(1000), pre(1010)])
+    );
+
+    // Absorption
+    assert_eq!(
+        Or(vec![pre(1000), And(vec![pre(1000), pre(1010)])]).normalize(),
+        pre(1000)
+    );
+    assert_eq!(
+        And(vec![Or(vec![pre(1010), pre(1000)]), pre(1000)]).normalize(),
+        pre(1000)
+    );
+
+    // Stronger and weaker forms of the same leaf
+    assert_eq!(
+        And(vec![CoCourse(CC!("TEST", 1000)), pre(1000)]).normalize(),
+        pre(1000)
+    );
+    assert_eq!(
+        Or(vec![PreCourseGrade(CC!("TEST", 1000), GR!(B)), pre(1000)]).normalize(),
+        pre(1000)
+    );
+    assert_eq!(
+        And(vec![
+            PreCourseGrade(CC!("TEST", 1000), GR!(C)),
+            PreCourseGrade(CC!("TEST", 1000), GR!(B -)),
+        ])
+        .normalize(),
+        PreCourseGrade(CC!("TEST", 1000), GR!(B -))
+    );
+    assert_eq!(
+        And(vec![CreditsEarned(30), Standing(ClassStanding::junior())]).normalize(),
+        Standing(ClassStanding::junior())
+    );
+
+    assert_eq!(Or(vec![NotRequired, pre(1000)]).normalize(), NotRequired);
+    assert_eq!(And(vec![NotRequired, pre(1000)]).normalize(), pre(1000));
+    assert_eq!(And(vec![]).normalize(), NotRequired);
+}
+
+#[test]
+fn test_equivalence() {
+    // Distributivity
+    let factored = And(vec![pre(1000), Or(vec![pre(1010), pre(1020)])]);
+    let expanded = Or(vec![
+        And(vec![pre(1000), pre(1010)]),
+        And(vec![pre(1020), pre(1000)]),
+    ]);
+    assert!(factored.is_equivalent(&expanded));
+    assert!(expanded.is_equivalent(&factored));
+
+    assert!(And(vec![pre(1000), pre(1010)]).is_equivalent(&And(vec![pre(1010), pre(1000)])));
+    assert!(!And(vec![pre(1000), pre(1010)]).is_equivalent(&Or(vec![pre(1000), pre(1010)])));
+    assert!(!pre(1000).is_equivalent(&CoCourse(CC!("TEST", 1000))));
+    assert!(CreditsEarned(60).is_equivalent(&Standing(ClassStanding::at_least(60))));
+
+    // Normalizing never changes what a requirement means, and is idempotent
+    for (code, req) in builtin_catalog().prereqs {
+        let normalized = req.normalize();
+        assert!(
+            req.is_equivalent(&normalized),
+            "{code}: {req} vs {normalized}"
+        );
+        assert_eq!(normalized.normalize(), normalized, "{code}");
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use ross_core::prereq_graph::{EdgeKind, GraphNode, GraphScope};
+use ross_core::prereqs::CourseReq::*;
+use ross_core::prereqs::{Grade, GradeLetter, GradeQualifier, Standing};
+use ross_core::schedule::{Catalog, CourseCode, CourseInfo, CourseTermOffering, Program};
+use ross_core::{CC, GR};
+
+fn test_catalog() -> Catalog {
+    Catalog {
+        programs: vec![Program {
+            name: "BS Test".to_string(),
+            semesters: vec![vec![CC!("TEST", 1000)], vec![CC!("TEST", 2000)]],
+            electives: vec![],
+            assoc_stems: vec!["TEST".to_string()],
+        }],
+        prereqs: HashMap::from([
+            (
+                CC!("TEST", 2000),
+                And(vec![
+                    PreCourse(CC!("TEST", 1000)),
+                    Or(vec![
+                        PreCourseGrade(CC!("MATH", 1300), GR!(C)),
+                        And(vec![
+                            CoCourse(CC!("MATH", 1200)),
+                            Standing(Standing::junior()),
+                        ]),
+                    ]),
+                ]),
+            ),
+            (CC!("MATH", 1300), PreCourse(CC!("MATH", 1200))),
+            (CC!("TEST", 3000), PreCourse(CC!("TEST", 2000))),
+        ]),
+        courses: [
+            (CC!("TEST", 1000), "Testing I"),
+            (CC!("TEST", 2000), "Testing II"),
+            (CC!("TEST", 3000), "Testing III"),
+            (CC!("MATH", 1200), "Precalculus"),
+            (CC!("MATH", 1300), "Calculus I"),
+        ]
+        .into_iter()
+        .map(|(code, title)| {
+            (
+                code,
+                CourseInfo::new(title, Some(3), CourseTermOffering::Both),
+            )
+        })
+        .collect(),
+        ..Catalog::new(2025)
+    }
+}
+
+#[test]
+fn test_program_graph() {
+    let graph = test_catalog()
+        .prereq_graph(&GraphScope::Program("BS Test".to_string()))
+        .unwrap();
+
+    // Program courses plus the prerequisites they name; MATH-1300's own prereqs are out of scope
+    let courses: Vec<(String, bool)> = graph
+        .courses
+        .iter()
+        .map(|c| (c.code.to_string(), c.in_scope))
+        .collect();
+    assert_eq!(
+        courses,
+        vec![
+            ("MATH-1200".to_string(), false),
+            ("MATH-1300".to_string(), false),
+            ("TEST-1000".to_string(), true),
+            ("TEST-2000".to_string(), true),
+        ]
+    );
+    assert_eq!(graph.groups.len(), 2);
+    assert!(graph.groups[0].any && !graph.groups[1].any);
+    assert_eq!(graph.conditions, vec!["Junior standing".to_string()]);
+
+    let grade_edge = graph
+        .edges
+        .iter()
+        .find(|e| e.from == GraphNode::Course(CC!("MATH", 1300)))
+        .unwrap();
+    assert_eq!(grade_edge.to, GraphNode::Group(0));
+    assert_eq!(grade_edge.label().as_deref(), Some("pre, C or better"));
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| e.from == GraphNode::Course(CC!("MATH", 1200))
+                && e.to == GraphNode::Group(1)
+                && e.kind == EdgeKind::Co)
+    );
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph prereqs {"), "{dot}");
+    assert!(
+        dot.contains("TEST_2000 [label=\"TEST-2000\\nTesting II\"];"),
+        "{dot}"
+    );
+    assert!(
+        dot.contains("MATH_1300 -> group0 [label=\"pre, C or better\"];"),
+        "{dot}"
+    );
+    assert!(
+        dot.contains("MATH_1200 -> group1 [label=\"co\", style=dashed];"),
+        "{dot}"
+    );
+    assert!(dot.contains("group0 -> TEST_2000;"), "{dot}");
+
+    let mermaid = graph.to_mermaid();
+    assert!(mermaid.starts_with("flowchart LR\n"), "{mermaid}");
+    assert!(mermaid.contains("group0{\"one of\"}"), "{mermaid}");
+    assert!(mermaid.contains("cond0>\"Junior standing\"]"), "{mermaid}");
+    assert!(mermaid.contains("MATH_1200 -.->|co| group1"), "{mermaid}");
+    assert!(
+        mermaid.contains("TEST_1000 -->|pre| TEST_2000"),
+        "{mermaid}"
+    );
+    assert!(
+        mermaid.contains("class MATH_1200,MATH_1300 outside"),
+        "{mermaid}"
+    );
+}
+
+#[test]
+fn test_scopes() {
+    let catalog = test_catalog();
+
+    // The closure follows prerequisites of prerequisites, but not courses depending on the course
+    let graph = catalog
+        .prereq_graph(&GraphScope::Closure(CC!("TEST", 2000)))
+        .unwrap();
+    assert!(graph.courses.iter().all(|c| c.in_scope));
+    assert_eq!(graph.courses.len(), 4);
+    assert!(
+        graph
+            .edges
+            .iter()
+            .any(|e| e.from == GraphNode::Course(CC!("MATH", 1200))
+                && e.to == GraphNode::Course(CC!("MATH", 1300)))
+    );
+
+    let graph = catalog
+        .prereq_graph(&GraphScope::Stem("MATH".to_string()))
+        .unwrap();
+    assert_eq!(graph.courses.len(), 2);
+    assert_eq!(graph.edges.len(), 1);
+
+    let graph = catalog.prereq_graph(&GraphScope::Catalog).unwrap();
+    assert_eq!(graph.courses.len(), 5);
+
+    assert!(
+        catalog
+            .prereq_graph(&GraphScope::Program("BS Missing".to_string()))
+            .is_err()
+    );
+    assert!(
+        catalog
+            .prereq_graph(&GraphScope::Closure(CC!("TEST", 9999)))
+            .is_err()
+    );
+}
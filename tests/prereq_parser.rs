@@ -0,0 +1,121 @@
+use ross_core::load_catalogs::builtin_catalog;
+use ross_core::prereq_parser::{parse_prereq_texts, parse_prereqs};
+use ross_core::prereqs::{CourseReq, Grade, GradeLetter, GradeQualifier, Standing};
+use ross_core::schedule::CourseCode;
+use ross_core::{CC, GR};
+
+#[test]
+fn test_parse_catalog_text() {
+    let req = parse_prereqs(
+        "CHEM-1210 and (MATH-1350 or MATH-1300 with a grade of C or better), concurrent enrollment in CHEM-3301",
+        "CHEM",
+    )
+    .unwrap();
+    assert_eq!(
+        req,
+        CourseReq::And(vec![
+            CourseReq::PreCourse(CC!("CHEM", 1210)),
+            CourseReq::Or(vec![
+                CourseReq::PreCourse(CC!("MATH", 1350)),
+                CourseReq::PreCourseGrade(CC!("MATH", 1300), GR!(C)),
+            ]),
+            CourseReq::CoCourse(CC!("CHEM", 3301)),
+        ])
+    );
+
+    // Bare numbers take the last stem mentioned, and labels and trailing periods are ignored
+    assert_eq!(
+        parse_prereqs("Prerequisites: PHYS 2210 or 2220; junior standing.", "CHEM").unwrap(),
+        CourseReq::And(vec![
+            CourseReq::Or(vec![
+                CourseReq::PreCourse(CC!("PHYS", 2210)),
+                CourseReq::PreCourse(CC!("PHYS", 2220)),
+            ]),
+            CourseReq::Standing(Standing::junior()),
+        ])
+    );
+    assert_eq!(
+        parse_prereqs("BIOL-1610, BIOL-1620, or consent of instructor", "BIOL").unwrap(),
+        CourseReq::Or(vec![
+            CourseReq::PreCourse(CC!("BIOL", 1610)),
+            CourseReq::PreCourse(CC!("BIOL", 1620)),
+            CourseReq::Instructor,
+        ])
+    );
+    assert_eq!(
+        parse_prereqs("Corequisite: CHEM-1200", "CHEM").unwrap(),
+        CourseReq::CoCourse(CC!("CHEM", 1200))
+    );
+    assert_eq!(
+        parse_prereqs("MATH-1210 (B- or better, or concurrently)", "MATH").unwrap(),
+        CourseReq::CoCourseGrade(CC!("MATH", 1210), GR!(B -))
+    );
+    assert_eq!(
+        parse_prereqs("ACT Math score of 24 or higher", "MATH").unwrap(),
+        CourseReq::TestScore("ACT Math".to_string(), 24)
+    );
+    assert_eq!(parse_prereqs("", "CHEM").unwrap(), CourseReq::NotRequired);
+}
+
+#[test]
+fn test_diagnostics() {
+    let err = parse_prereqs("CHEM-1210 and (MATH-1350", "CHEM").unwrap_err();
+    assert_eq!(err.message, "expected ')'");
+    assert_eq!(err.offset, 24);
+
+    let err = parse_prereqs("CHEM-1210 and a passing interview", "CHEM").unwrap_err();
+    assert_eq!(err.offset, 14);
+    assert!(err.to_string().contains("a passing interview"), "{err}");
+
+    let err = parse_prereqs(
+        "ACT Math score of 24 or higher or placement into MATH-1300",
+        "MATH",
+    )
+    .unwrap_err();
+    assert_eq!(err.message, "placement prerequisites are not supported");
+    assert_eq!(err.offset, 34);
+
+    let (parsed, diagnostics) = parse_prereq_texts([
+        (CC!("CHEM", 3310), "1220 with a C- or better"),
+        (CC!("CHEM", 3320), "CHEM-3310 & lab"),
+    ]);
+    assert_eq!(
+        parsed[&CC!("CHEM", 3310)],
+        CourseReq::PreCourseGrade(CC!("CHEM", 1220), GR!(C -))
+    );
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(diagnostics[0].0, CC!("CHEM", 3320));
+}
+
+#[test]
+fn test_display_round_trip() {
+    let reqs = [
+        CourseReq::And(vec![
+            CourseReq::Or(vec![
+                CourseReq::CoCourse(CC!("MATH", 1210)),
+                CourseReq::TestScore("ACT Math".to_string(), 28),
+            ]),
+            CourseReq::CoCourseGrade(CC!("PHYS", 2210), GR!(C+)),
+            CourseReq::Program("CHEM".to_string()),
+        ]),
+        CourseReq::Or(vec![
+            CourseReq::Standing(Standing::underclass()),
+            CourseReq::Standing(Standing::between(30, 89)),
+            CourseReq::Standing(Standing::at_least(45)),
+            CourseReq::CreditsEarned(12),
+        ]),
+        CourseReq::Instructor,
+        CourseReq::NotRequired,
+    ];
+    for req in reqs {
+        assert_eq!(parse_prereqs(&req.to_string(), "TEST").unwrap(), req);
+    }
+
+    // Everything in the compiled-in catalog reads back as itself
+    for (code, req) in builtin_catalog().prereqs {
+        let text = req.to_string();
+        let parsed = parse_prereqs(&text, &code.stem)
+            .unwrap_or_else(|e| panic!("{code}: could not parse \"{text}\": {e}"));
+        assert_eq!(parsed, req, "{code}: \"{text}\"");
+    }
+}
@@ -0,0 +1,55 @@
+use anyhow::Result;
+
+use ross_core::CC;
+use ross_core::geneds::ElectiveReq;
+use ross_core::load_catalogs::builtin_catalog;
+use ross_core::schedule::{CourseCode, UnmetElective, generate_schedule};
+
+#[test]
+fn test_unmet_program_electives() -> Result<()> {
+    let mut sched = generate_schedule(vec!["BA Chemistry"], builtin_catalog(), None)?;
+    assert_eq!(
+        sched.unmet_electives()?,
+        vec![UnmetElective {
+            program: "BA Chemistry".to_string(),
+            elective: "Advanced Courses".to_string(),
+        }]
+    );
+
+    // Two of the listed advanced courses satisfy the group
+    let last = sched.courses.len() - 1;
+    sched.courses[last].push(CC!("CHEM", 3150));
+    assert_eq!(sched.unmet_electives()?.len(), 1);
+    sched.courses[last].push(CC!("CHEM", 4350));
+    assert!(sched.unmet_electives()?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_solver_schedules_program_electives() -> Result<()> {
+    let mut sched = generate_schedule(vec!["BA Chemistry"], builtin_catalog(), None)?;
+    sched.validate()?;
+    assert!(sched.unmet_electives()?.is_empty());
+
+    // "Advanced Courses" asks for two of its listed courses
+    let catalog = sched.catalog.clone();
+    let program = catalog
+        .programs
+        .iter()
+        .find(|p| p.name == "BA Chemistry")
+        .unwrap();
+    let elective = program
+        .electives
+        .iter()
+        .find(|e| e.name == "Advanced Courses")
+        .unwrap();
+    let ElectiveReq::Courses { num, courses } = &elective.req else {
+        panic!("unexpected requirement {:?}", elective.req);
+    };
+    let scheduled = courses
+        .iter()
+        .filter(|code| sched.courses.iter().flatten().any(|c| c == *code))
+        .count();
+    assert!(scheduled >= *num);
+    Ok(())
+}
@@ -38,16 +38,11 @@ fn test_ross() -> Result<()> {
         if s == 0 {
             println!("Semester 0 (incoming only):");
         } else {
-            println!("Semester {s}");
+            println!("{}", sched.term(s - 1));
         }
         let mut sem_credits = 0;
         for code in semester {
-            let credits = sched
-                .catalog
-                .courses
-                .get(code)
-                .and_then(|(_, cr, _)| *cr)
-                .unwrap_or(0);
+            let credits = sched.course_credits(code);
             println!("  {code} ({credits} credits)");
             sem_credits += credits;
         }
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use std::collections::HashMap;
+
+use ross_core::CC;
+use ross_core::prereq_parser::parse_prereqs;
+use ross_core::prereqs::CourseReq;
+use ross_core::schedule::{Catalog, CourseCode, CourseInfo, CourseTermOffering, Schedule};
+
+fn test_schedule() -> Schedule {
+    let catalog = Catalog {
+        prereqs: HashMap::from([
+            (
+                CC!("CHEM", 1200),
+                CourseReq::SameTermCourse(CC!("CHEM", 1201)),
+            ),
+            (
+                CC!("CHEM", 1201),
+                CourseReq::SameTermCourse(CC!("CHEM", 1200)),
+            ),
+        ]),
+        courses: HashMap::from([
+            (
+                CC!("CHEM", 1200),
+                CourseInfo::new("General Chemistry I", Some(4), CourseTermOffering::Both),
+            ),
+            (
+                CC!("CHEM", 1201),
+                CourseInfo::new("General Chemistry I Lab", Some(1), CourseTermOffering::Both),
+            ),
+        ]),
+        ..Catalog::new(2025)
+    };
+    Schedule::new(
+        catalog,
+        vec![vec![CC!("CHEM", 1200)], vec![CC!("CHEM", 1201)]],
+    )
+}
+
+#[test]
+fn test_same_term_prereqs() -> Result<()> {
+    let mut sched = test_schedule();
+    // The lab a semester after the lecture would do for "or concurrently", but not for the same term
+    assert!(CourseReq::CoCourse(CC!("CHEM", 1200)).is_satisfied(&sched, &CC!("CHEM", 1201), 1));
+    assert_eq!(
+        sched.unmet_prereqs(),
+        vec![(0, &CC!("CHEM", 1200)), (1, &CC!("CHEM", 1201))]
+    );
+    let explanation =
+        sched.catalog.prereqs[&CC!("CHEM", 1201)].explain(&sched, &CC!("CHEM", 1201), 1);
+    assert_eq!(
+        explanation.missing.unwrap().to_string(),
+        "CHEM-1200 in Spring 2026"
+    );
+
+    sched.courses = vec![vec![CC!("CHEM", 1200), CC!("CHEM", 1201)], vec![]];
+    assert!(sched.validate_prereqs()?);
+
+    // A partner already taken counts
+    sched.courses = vec![vec![CC!("CHEM", 1201)], vec![]];
+    sched.incoming = vec![CC!("CHEM", 1200)];
+    assert!(sched.validate_prereqs()?);
+    Ok(())
+}
+
+#[test]
+fn test_same_term_text() {
+    let req = CourseReq::SameTermCourse(CC!("CHEM", 1201));
+    assert_eq!(req.to_string(), "CHEM-1201 (same term)");
+    assert_eq!(parse_prereqs(&req.to_string(), "CHEM").unwrap(), req);
+    assert_eq!(
+        parse_prereqs("CHEM-1201 must be taken in the same semester", "CHEM").unwrap(),
+        req
+    );
+    assert_eq!(
+        parse_prereqs("simultaneous enrollment in CHEM-1201", "CHEM").unwrap(),
+        req
+    );
+
+    // A course taken in the same term has no grade yet to meet a minimum
+    let err = parse_prereqs(
+        "CHEM-1201 (C or better) must be taken in the same semester",
+        "CHEM",
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.message,
+        "a minimum grade cannot be required of a course taken in the same term"
+    );
+    assert_eq!(err.offset, 0);
+    let err =
+        parse_prereqs("simultaneous enrollment in CHEM-1201 (C or better)", "CHEM").unwrap_err();
+    assert_eq!(err.offset, "simultaneous enrollment in ".len());
+
+    // Same term implies "or concurrently", but not the other way around
+    assert_eq!(
+        CourseReq::And(vec![req.clone(), CourseReq::CoCourse(CC!("CHEM", 1201))]).normalize(),
+        req
+    );
+    assert!(!req.is_equivalent(&CourseReq::CoCourse(CC!("CHEM", 1201))));
+}
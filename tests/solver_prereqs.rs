@@ -0,0 +1,180 @@
+use anyhow::Result;
+use std::collections::HashMap;
+
+use ross_core::prereqs::{CourseReq, Grade, GradeLetter, GradeQualifier};
+use ross_core::schedule::{
+    Catalog, CourseCode, CourseInfo, CourseTermOffering, Credits, Program, Schedule, Transcript,
+    TranscriptEntry,
+};
+use ross_core::{CC, GR};
+
+fn test_schedule(course: CourseCode, req: CourseReq) -> Schedule {
+    let info = |title, credits| CourseInfo::new(title, Some(credits), CourseTermOffering::Both);
+    let catalog = Catalog {
+        programs: vec![Program {
+            name: "BS Test".to_string(),
+            semesters: vec![],
+            electives: vec![],
+            assoc_stems: vec!["TEST".to_string()],
+        }],
+        prereqs: HashMap::from([(course.clone(), req)]),
+        courses: HashMap::from([
+            (CC!("TEST", 1000), info("Lecture I", 3)),
+            (CC!("TEST", 1010), info("Lecture II", 3)),
+            (CC!("TEST", 1020), info("Combined Lecture", 4)),
+            (course.clone(), info("Advanced Topics", 3)),
+        ]),
+        ..Catalog::new(2025)
+    };
+    Schedule {
+        programs: vec!["BS Test".to_string()],
+        ..Schedule::new(catalog, vec![vec![course], vec![], vec![], vec![]])
+    }
+}
+
+fn scheduled(sched: &Schedule, code: &CourseCode) -> Option<usize> {
+    sched.courses.iter().position(|sem| sem.contains(code))
+}
+
+#[test]
+fn test_nested_or_of_and() -> Result<()> {
+    // Either both of TEST-1000 and TEST-1010, or TEST-1020 alone, which is fewer credits
+    let req = CourseReq::Or(vec![
+        CourseReq::And(vec![
+            CourseReq::PreCourse(CC!("TEST", 1000)),
+            CourseReq::PreCourse(CC!("TEST", 1010)),
+        ]),
+        CourseReq::PreCourse(CC!("TEST", 1020)),
+    ]);
+    let mut sched = test_schedule(CC!("TEST", 3000), req);
+    sched.validate()?;
+
+    assert_eq!(scheduled(&sched, &CC!("TEST", 1000)), None);
+    assert_eq!(scheduled(&sched, &CC!("TEST", 1010)), None);
+    let lecture = scheduled(&sched, &CC!("TEST", 1020)).unwrap();
+    assert!(lecture < scheduled(&sched, &CC!("TEST", 3000)).unwrap());
+    assert!(sched.validate_prereqs()?);
+    Ok(())
+}
+
+#[test]
+fn test_solution_meets_prereqs() -> Result<()> {
+    let req = CourseReq::And(vec![
+        CourseReq::PreCourseGrade(CC!("TEST", 1000), GR!(C)),
+        CourseReq::CoCourse(CC!("TEST", 1010)),
+        CourseReq::CreditsEarned(6),
+    ]);
+    let mut sched = test_schedule(CC!("TEST", 3000), req);
+    sched.validate()?;
+    assert!(sched.unmet_prereqs().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_program_prereqs() -> Result<()> {
+    let req = CourseReq::Or(vec![
+        CourseReq::And(vec![
+            CourseReq::Program("TEST".to_string()),
+            CourseReq::PreCourse(CC!("TEST", 1000)),
+        ]),
+        CourseReq::And(vec![
+            CourseReq::Program("CHEM".to_string()),
+            CourseReq::PreCourse(CC!("TEST", 1020)),
+        ]),
+    ]);
+    let mut sched = test_schedule(CC!("TEST", 3000), req);
+    sched.validate()?;
+    assert!(scheduled(&sched, &CC!("TEST", 1000)).is_some());
+    assert_eq!(scheduled(&sched, &CC!("TEST", 1020)), None);
+
+    // Restricted to another program's majors, the course cannot be scheduled at all
+    let mut sched = test_schedule(CC!("TEST", 3000), CourseReq::Program("CHEM".to_string()));
+    assert!(sched.validate().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_program_in_nested_prereqs() -> Result<()> {
+    // Majors of the program skip the lecture
+    let req = CourseReq::Or(vec![
+        CourseReq::Program("TEST".to_string()),
+        CourseReq::PreCourse(CC!("TEST", 1020)),
+    ]);
+    let mut sched = test_schedule(CC!("TEST", 3000), req);
+    sched.validate()?;
+    assert_eq!(scheduled(&sched, &CC!("TEST", 1020)), None);
+    assert!(sched.validate_prereqs()?);
+
+    // Other majors take the lecture the restriction stands in for, besides the unrestricted one
+    let req = CourseReq::And(vec![
+        CourseReq::Or(vec![
+            CourseReq::Program("CHEM".to_string()),
+            CourseReq::PreCourse(CC!("TEST", 1000)),
+        ]),
+        CourseReq::PreCourse(CC!("TEST", 1010)),
+    ]);
+    let mut sched = test_schedule(CC!("TEST", 3000), req);
+    sched.validate()?;
+    let course = scheduled(&sched, &CC!("TEST", 3000)).unwrap();
+    assert!(scheduled(&sched, &CC!("TEST", 1000)).unwrap() < course);
+    assert!(scheduled(&sched, &CC!("TEST", 1010)).unwrap() < course);
+    assert!(sched.validate_prereqs()?);
+    Ok(())
+}
+
+#[test]
+fn test_same_term_prereqs() -> Result<()> {
+    let mut sched = test_schedule(
+        CC!("TEST", 3000),
+        CourseReq::SameTermCourse(CC!("TEST", 1000)),
+    );
+    sched.validate()?;
+    let lab = scheduled(&sched, &CC!("TEST", 1000)).unwrap();
+    assert_eq!(Some(lab), scheduled(&sched, &CC!("TEST", 3000)));
+    assert!(sched.validate_prereqs()?);
+    Ok(())
+}
+
+#[test]
+fn test_incoming_variable_credits() -> Result<()> {
+    // Incoming research counts its minimum credit, and the solver does not pick another value for it
+    let mut sched = test_schedule(CC!("TEST", 3000), CourseReq::CreditsEarned(1));
+    let mut research = CourseInfo::new("Research", None, CourseTermOffering::Both);
+    research.credits = Some(Credits::range(1, 3));
+    sched.catalog.courses.insert(CC!("TEST", 4990), research);
+    sched.incoming = vec![CC!("TEST", 4990)];
+    sched.validate()?;
+
+    assert!(!sched.credits.contains_key(&CC!("TEST", 4990)));
+    assert_eq!(sched.course_credits(&CC!("TEST", 4990)), 1);
+    assert!(sched.validate_prereqs()?);
+    Ok(())
+}
+
+#[test]
+fn test_failed_grade_gate() -> Result<()> {
+    // TEST-1000 was passed with a C-, so it has to be retaken before TEST-3000
+    let req = CourseReq::PreCourseGrade(CC!("TEST", 1000), GR!(C));
+    let transcript = Transcript {
+        courses: vec![TranscriptEntry {
+            code: CC!("TEST", 1000),
+            grade: Some(GR!(C-)),
+        }],
+    };
+    let mut sched = test_schedule(CC!("TEST", 3000), req.clone());
+    sched.set_transcript(transcript.clone());
+    sched.validate()?;
+    let retake = scheduled(&sched, &CC!("TEST", 1000)).unwrap();
+    assert!(retake < scheduled(&sched, &CC!("TEST", 3000)).unwrap());
+    assert!(sched.unmet_prereqs().is_empty());
+
+    // With no semester left for the retake, the error names the course and grade
+    let mut sched = test_schedule(CC!("TEST", 3000), req);
+    sched.courses.truncate(1);
+    sched.set_transcript(transcript);
+    let err = sched.validate().unwrap_err().to_string();
+    assert!(
+        err.contains("TEST-3000 needs TEST-1000 with C or better, but the transcript shows C-")
+    );
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use ross_core::CC;
+use ross_core::prereqs::{CourseReq, Standing};
+use ross_core::schedule::{Catalog, CourseCode, CourseInfo, CourseTermOffering, Schedule};
+
+fn course(num: usize) -> CourseCode {
+    CC!("TEST", num)
+}
+
+// Ten 15-credit semesters of one course each, so 15 * n credits are earned before semester n
+fn test_schedule() -> Schedule {
+    let catalog = Catalog {
+        courses: (1000..1010)
+            .map(|num| {
+                (
+                    course(num),
+                    CourseInfo::new("Block", Some(15), CourseTermOffering::Both),
+                )
+            })
+            .collect(),
+        ..Catalog::new(2025)
+    };
+    Schedule::new(catalog, (1000..1010).map(|num| vec![course(num)]).collect())
+}
+
+#[test]
+fn test_standing() {
+    let mut sched = test_schedule();
+    assert_eq!(sched.credits_before(0), 0);
+    assert_eq!(sched.credits_before(4), 60);
+
+    let junior = CourseReq::Standing(Standing::junior());
+    assert!(!junior.is_satisfied(&sched, &CC!("TEST", 2000), 3));
+    assert!(junior.is_satisfied(&sched, &CC!("TEST", 2000), 4));
+
+    let underclass = CourseReq::Standing(Standing::underclass());
+    assert!(underclass.is_satisfied(&sched, &CC!("TEST", 2000), 3));
+    assert!(!underclass.is_satisfied(&sched, &CC!("TEST", 2000), 4));
+
+    let range = CourseReq::Standing(Standing::between(30, 45));
+    assert!(!range.is_satisfied(&sched, &CC!("TEST", 2000), 1));
+    assert!(range.is_satisfied(&sched, &CC!("TEST", 2000), 2));
+    assert!(range.is_satisfied(&sched, &CC!("TEST", 2000), 3));
+    assert!(!range.is_satisfied(&sched, &CC!("TEST", 2000), 4));
+
+    // Incoming credit counts towards standing
+    sched.incoming.push(course(1009));
+    sched.courses.pop();
+    assert!(junior.is_satisfied(&sched, &CC!("TEST", 2000), 3));
+}
+
+#[test]
+fn test_standing_display() {
+    assert_eq!(Standing::junior().to_string(), "Junior standing");
+    assert_eq!(Standing::senior().to_string(), "Senior standing");
+    assert_eq!(Standing::underclass().to_string(), "Fr/So standing");
+    assert_eq!(
+        Standing::between(30, 45).to_string(),
+        "30 to 45 credits earned"
+    );
+}
@@ -0,0 +1,107 @@
+use std::collections::HashMap;
+
+use ross_core::CC;
+use ross_core::schedule::{
+    Catalog, CourseCode, CourseInfo, CourseTermOffering, Program, Schedule, Season, SummerOptions,
+    Term,
+};
+
+#[test]
+fn test_alternate_year_offerings() {
+    let fall_25 = Term::new(Season::Fall, 2025);
+    let spring_26 = Term::new(Season::Spring, 2026);
+    let fall_26 = Term::new(Season::Fall, 2026);
+
+    assert!(CourseTermOffering::FallOdd.is_offered(&fall_25));
+    assert!(!CourseTermOffering::FallOdd.is_offered(&fall_26));
+    assert!(CourseTermOffering::FallEven.is_offered(&fall_26));
+    assert!(CourseTermOffering::SpringEven.is_offered(&spring_26));
+    assert!(!CourseTermOffering::SpringOdd.is_offered(&spring_26));
+    assert!(!CourseTermOffering::Both.is_offered(&Term::new(Season::Summer, 2026)));
+
+    let listed = CourseTermOffering::Terms(vec![spring_26]);
+    assert!(listed.is_offered(&spring_26));
+    assert!(!listed.is_offered(&Term::new(Season::Spring, 2028)));
+}
+
+#[test]
+fn test_schedule_terms() {
+    let catalog = Catalog {
+        programs: vec![Program {
+            name: "BS Test".to_string(),
+            semesters: vec![vec![CC!("PHYS", 4100)]],
+            electives: vec![],
+            assoc_stems: vec!["PHYS".to_string()],
+        }],
+        courses: HashMap::from([(
+            CC!("PHYS", 4100),
+            CourseInfo::new("Quantum Mechanics", Some(3), CourseTermOffering::FallOdd),
+        )]),
+        ..Catalog::new(2025)
+    };
+    let mut sched = Schedule {
+        programs: vec!["BS Test".to_string()],
+        ..Schedule::new(catalog, vec![vec![], vec![], vec![CC!("PHYS", 4100)]])
+    };
+
+    assert_eq!(sched.term(0), Term::new(Season::Fall, 2025));
+    assert_eq!(sched.term(1), Term::new(Season::Spring, 2026));
+    assert_eq!(sched.term(2), Term::new(Season::Fall, 2026));
+    assert!(Term::new(Season::Spring, 2026) < Term::new(Season::Fall, 2026));
+
+    // Fall 2026 is an even year, so the course is not taught then
+    assert!(!sched.is_valid().unwrap());
+    sched.start = Term::new(Season::Fall, 2024);
+    assert!(sched.is_valid().unwrap());
+
+    // A spring entrant reaches Fall 2025 in their second semester
+    sched.start = Term::new(Season::Spring, 2025);
+    assert_eq!(sched.term(1), Term::new(Season::Fall, 2025));
+    assert_eq!(sched.term(2), Term::new(Season::Spring, 2026));
+    assert!(!sched.is_valid().unwrap());
+    sched.courses.swap(1, 2);
+    assert!(sched.is_valid().unwrap());
+    assert_eq!(sched.term(1).to_string(), "Fall 2025");
+    assert_eq!(sched.term(1).academic_year(), 2025);
+    assert_eq!(sched.term(0).academic_year(), 2024);
+}
+
+#[test]
+fn test_summer_terms() -> anyhow::Result<()> {
+    let catalog = Catalog {
+        courses: HashMap::from([(
+            CC!("BIOL", 4990),
+            CourseInfo::new("Field Biology", Some(4), CourseTermOffering::Summer),
+        )]),
+        ..Catalog::new(2025)
+    };
+    let mut sched = Schedule::new(catalog, vec![vec![]; 4]);
+
+    sched.set_summer_options(SummerOptions {
+        enabled: true,
+        ..Default::default()
+    })?;
+    let terms: Vec<String> = (0..sched.courses.len())
+        .map(|sem_idx| sched.term(sem_idx).to_string())
+        .collect();
+    assert_eq!(
+        terms,
+        [
+            "Fall 2025",
+            "Spring 2026",
+            "Summer 2026",
+            "Fall 2026",
+            "Spring 2027"
+        ]
+    );
+
+    sched.courses[2].push(CC!("BIOL", 4990));
+    assert!(sched.is_valid()?);
+    assert!(sched.set_summer_options(SummerOptions::default()).is_err());
+
+    sched.courses[2].clear();
+    sched.set_summer_options(SummerOptions::default())?;
+    assert_eq!(sched.courses.len(), 4);
+    assert_eq!(sched.term(2), Term::new(Season::Fall, 2026));
+    Ok(())
+}
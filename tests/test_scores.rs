@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+
+use ross_core::CC;
+use ross_core::prereqs::CourseReq;
+use ross_core::schedule::{Catalog, CourseCode, CourseInfo, CourseTermOffering, Schedule};
+
+fn test_schedule() -> Schedule {
+    let catalog = Catalog {
+        courses: HashMap::from([
+            (
+                CC!("GNST", 1000),
+                CourseInfo::new("Transfer Credit", Some(30), CourseTermOffering::Both),
+            ),
+            (
+                CC!("MATH", 1300),
+                CourseInfo::new("Calculus I", Some(4), CourseTermOffering::Both),
+            ),
+            (
+                CC!("MATH", 1350),
+                CourseInfo::new("Calculus II", Some(4), CourseTermOffering::Both),
+            ),
+        ]),
+        ..Catalog::new(2025)
+    };
+    Schedule {
+        incoming: vec![CC!("GNST", 1000)],
+        ..Schedule::new(
+            catalog,
+            vec![vec![CC!("MATH", 1300)], vec![CC!("MATH", 1350)]],
+        )
+    }
+}
+
+#[test]
+fn test_test_score_prereqs() {
+    let mut sched = test_schedule();
+    let req = CourseReq::TestScore("ACT Math".to_string(), 24);
+    assert!(!req.is_satisfied(&sched, &CC!("MATH", 1300), 0));
+
+    sched.set_test_score("ACT Math", 23);
+    assert!(!req.is_satisfied(&sched, &CC!("MATH", 1300), 0));
+    sched.set_test_score("ACT Math", 24);
+    assert!(req.is_satisfied(&sched, &CC!("MATH", 1300), 0));
+    assert_eq!(req.to_string(), "ACT Math score of 24 or higher");
+}
+
+#[test]
+fn test_credits_earned_prereqs() {
+    let sched = test_schedule();
+    let req = CourseReq::CreditsEarned(34);
+    // 30 incoming credits before the first semester, 34 before the second
+    assert!(!req.is_satisfied(&sched, &CC!("MATH", 1300), 0));
+    assert!(req.is_satisfied(&sched, &CC!("MATH", 1350), 1));
+    assert_eq!(req.to_string(), "completion of 34 credit hours");
+}
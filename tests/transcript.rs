@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use ross_core::prereqs::{CourseReq, Grade, GradeLetter, GradeQualifier};
+use ross_core::schedule::{Catalog, CourseCode, Schedule, Transcript, TranscriptEntry};
+use ross_core::{CC, GR};
+
+fn test_schedule() -> Schedule {
+    let catalog = Catalog {
+        prereqs: HashMap::from([
+            (
+                CC!("CHEM", 1220),
+                CourseReq::PreCourseGrade(CC!("CHEM", 1210), GR!(C)),
+            ),
+            (
+                CC!("CHEM", 2200),
+                CourseReq::PreCourseGrade(CC!("CHEM", 1220), GR!(C)),
+            ),
+        ]),
+        ..Catalog::new(2025)
+    };
+    Schedule::new(
+        catalog,
+        vec![vec![CC!("CHEM", 1220)], vec![CC!("CHEM", 2200)]],
+    )
+}
+
+fn transcript(grade: Option<Grade>) -> Transcript {
+    Transcript {
+        courses: vec![TranscriptEntry {
+            code: CC!("CHEM", 1210),
+            grade,
+        }],
+    }
+}
+
+#[test]
+fn test_grade_ordering() {
+    assert!(GR!(A) > GR!(A-));
+    assert!(GR!(B+) > GR!(B));
+    assert!(GR!(B) > GR!(B-));
+    assert!(GR!(B-) > GR!(C+));
+}
+
+#[test]
+fn test_grade_gated_prereqs() {
+    let req = CourseReq::PreCourseGrade(CC!("CHEM", 1210), GR!(C));
+
+    let mut sched = test_schedule();
+    sched.set_transcript(transcript(Some(GR!(C-))));
+    assert_eq!(sched.incoming, vec![CC!("CHEM", 1210)]);
+    assert!(!req.is_satisfied(&sched, &CC!("CHEM", 1220), 0));
+
+    sched.set_transcript(transcript(Some(GR!(C))));
+    assert!(req.is_satisfied(&sched, &CC!("CHEM", 1220), 0));
+    assert!(!req.explain(&sched, &CC!("CHEM", 1220), 0).provisional);
+
+    // Incoming credit without a transcript grade counts as passed
+    sched.transcript = Transcript::default();
+    assert!(req.is_satisfied(&sched, &CC!("CHEM", 1220), 0));
+    assert!(!req.explain(&sched, &CC!("CHEM", 1220), 0).provisional);
+}
+
+#[test]
+fn test_retaken_grade_gate() {
+    let req = CourseReq::PreCourseGrade(CC!("CHEM", 1210), GR!(C));
+    let mut sched = test_schedule();
+    sched.set_transcript(transcript(Some(GR!(C-))));
+
+    // Retaking the course a semester earlier meets the gate, pending the new grade
+    sched.courses.insert(0, vec![CC!("CHEM", 1210)]);
+    assert!(req.is_satisfied(&sched, &CC!("CHEM", 1220), 1));
+    assert!(req.explain(&sched, &CC!("CHEM", 1220), 1).provisional);
+
+    // A retake in the same semester is too late
+    sched.courses = vec![vec![CC!("CHEM", 1210), CC!("CHEM", 1220)]];
+    assert!(!req.is_satisfied(&sched, &CC!("CHEM", 1220), 0));
+}
+
+#[test]
+fn test_provisional_plan() {
+    // CHEM-1220 is only planned, so CHEM-2200 relies on a grade nobody has earned yet
+    let mut sched = test_schedule();
+    sched.set_transcript(transcript(Some(GR!(B))));
+    let audit = sched.audit().unwrap();
+    assert!(audit.is_satisfied());
+    assert!(audit.is_provisional());
+    let provisional: Vec<_> = audit
+        .entries
+        .iter()
+        .filter(|e| e.provisional)
+        .map(|e| e.requirement.as_str())
+        .collect();
+    assert_eq!(provisional, vec!["CHEM-2200"]);
+
+    // A course in progress is not graded either
+    sched.set_transcript(transcript(None));
+    let audit = sched.audit().unwrap();
+    assert!(audit.is_satisfied());
+    assert!(audit.entries.iter().filter(|e| e.provisional).count() == 2);
+}
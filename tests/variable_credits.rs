@@ -0,0 +1,61 @@
+use anyhow::Result;
+use std::collections::HashMap;
+
+use ross_core::CC;
+use ross_core::geneds::{ElectiveReq, GenEd};
+use ross_core::schedule::{
+    Catalog, CourseCode, CourseInfo, CourseTermOffering, Credits, Program, Schedule,
+};
+
+fn test_schedule() -> Schedule {
+    let mut research = CourseInfo::new("Undergraduate Research", None, CourseTermOffering::Both);
+    research.credits = Some(Credits::range(1, 3));
+    let catalog = Catalog {
+        programs: vec![Program {
+            name: "BS Test".to_string(),
+            semesters: vec![vec![CC!("TEST", 1000)], vec![CC!("TEST", 4990)]],
+            electives: vec![],
+            assoc_stems: vec!["TEST".to_string()],
+        }],
+        geneds: vec![GenEd::Core {
+            name: "Depth".to_string(),
+            req: ElectiveReq::Credits {
+                num: 6,
+                courses: vec![CC!("TEST", 1000), CC!("TEST", 4990)],
+            },
+        }],
+        courses: HashMap::from([
+            (
+                CC!("TEST", 1000),
+                CourseInfo::new("Testing I", Some(3), CourseTermOffering::Both),
+            ),
+            (CC!("TEST", 4990), research),
+        ]),
+        ..Catalog::new(2025)
+    };
+    Schedule {
+        programs: vec!["BS Test".to_string()],
+        ..Schedule::new(
+            catalog,
+            vec![vec![CC!("TEST", 1000)], vec![CC!("TEST", 4990)]],
+        )
+    }
+}
+
+#[test]
+fn test_variable_credits() -> Result<()> {
+    let mut sched = test_schedule();
+
+    // Without a choice the course counts its minimum, which is not enough for the GenEd
+    assert_eq!(sched.course_credits(&CC!("TEST", 4990)), 1);
+    assert!(!sched.is_valid()?);
+
+    sched.credits.insert(CC!("TEST", 4990), 3);
+    assert_eq!(sched.course_credits(&CC!("TEST", 4990)), 3);
+    assert!(sched.is_valid()?);
+
+    // Chosen credits must lie within the catalog range
+    sched.credits.insert(CC!("TEST", 4990), 4);
+    assert!(!sched.is_valid()?);
+    Ok(())
+}